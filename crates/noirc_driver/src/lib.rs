@@ -7,7 +7,7 @@ use acvm::Language;
 use clap::Args;
 use contract::ContractFunction;
 use fm::FileType;
-use iter_extended::try_vecmap;
+use iter_extended::{try_vecmap, vecmap};
 use noirc_abi::FunctionSignature;
 use noirc_errors::{reporter, ReportedError};
 use noirc_evaluator::{create_circuit, ssa_refactor::experimental_create_circuit};
@@ -253,7 +253,16 @@ impl Driver {
         options: &CompileOptions,
         main_function: FuncId,
     ) -> Result<CompiledProgram, ReportedError> {
-        let program = monomorphize(main_function, &self.context.def_interner);
+        let program = match monomorphize(main_function, &self.context.def_interner) {
+            Ok(program) => program,
+            Err(errors) => {
+                let errors = vecmap(errors, |error| error.into_file_diagnostic());
+                let files = &self.context.file_manager;
+                let error_count = reporter::report_all(files, &errors, options.allow_warnings);
+                reporter::finish_report(error_count)?;
+                return Err(ReportedError);
+            }
+        };
 
         let np_language = self.language.clone();
         let is_opcode_supported = acvm::default_is_opcode_supported(np_language.clone());
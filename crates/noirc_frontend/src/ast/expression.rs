@@ -12,6 +12,7 @@ pub enum ExpressionKind {
     Block(BlockExpression),
     Prefix(Box<PrefixExpression>),
     Index(Box<IndexExpression>),
+    Slice(Box<SliceExpression>),
     Call(Box<CallExpression>),
     MethodCall(Box<MethodCallExpression>),
     Constructor(Box<ConstructorExpression>),
@@ -19,6 +20,7 @@ pub enum ExpressionKind {
     Cast(Box<CastExpression>),
     Infix(Box<InfixExpression>),
     For(Box<ForExpression>),
+    While(Box<WhileExpression>),
     If(Box<IfExpression>),
     Variable(Path),
     Tuple(Vec<Expression>),
@@ -64,6 +66,10 @@ impl ExpressionKind {
         ExpressionKind::Literal(Literal::Integer(contents))
     }
 
+    pub fn field(contents: FieldElement) -> ExpressionKind {
+        ExpressionKind::Literal(Literal::Field(contents))
+    }
+
     pub fn boolean(contents: bool) -> ExpressionKind {
         ExpressionKind::Literal(Literal::Bool(contents))
     }
@@ -146,14 +152,34 @@ impl Expression {
         Expression::new(kind, span)
     }
 
+    pub fn slice(
+        collection: Expression,
+        start: Expression,
+        end: Expression,
+        span: Span,
+    ) -> Expression {
+        let kind = ExpressionKind::Slice(Box::new(SliceExpression { collection, start, end }));
+        Expression::new(kind, span)
+    }
+
     pub fn cast(lhs: Expression, r#type: UnresolvedType, span: Span) -> Expression {
         let kind = ExpressionKind::Cast(Box::new(CastExpression { lhs, r#type }));
         Expression::new(kind, span)
     }
 
-    pub fn call(lhs: Expression, arguments: Vec<Expression>, span: Span) -> Expression {
+    /// Creates a call expression. The arguments are either all positional or,
+    /// as in `foo(x: 1, y: 2)`, all named.
+    pub fn call(
+        lhs: Expression,
+        arguments: Vec<(Option<Ident>, Expression)>,
+        span: Span,
+    ) -> Expression {
         let func = Box::new(lhs);
-        let kind = ExpressionKind::Call(Box::new(CallExpression { func, arguments }));
+        let (names, arguments): (Vec<_>, Vec<_>) = arguments.into_iter().unzip();
+        let argument_names = names.into_iter().collect::<Option<Vec<_>>>();
+        let argument_names = argument_names.filter(|names| !names.is_empty());
+        let kind =
+            ExpressionKind::Call(Box::new(CallExpression { func, arguments, argument_names }));
         Expression::new(kind, span)
     }
 }
@@ -166,6 +192,12 @@ pub struct ForExpression {
     pub block: Expression,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WhileExpression {
+    pub condition: Expression,
+    pub block: Expression,
+}
+
 pub type BinaryOp = Spanned<BinaryOpKind>;
 
 #[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
@@ -204,6 +236,50 @@ impl BinaryOpKind {
         )
     }
 
+    /// True for the comparators `<`, `<=`, `>` and `>=` which order their operands
+    pub fn is_ordering(&self) -> bool {
+        matches!(
+            self,
+            BinaryOpKind::Less
+                | BinaryOpKind::LessEqual
+                | BinaryOpKind::Greater
+                | BinaryOpKind::GreaterEqual
+        )
+    }
+
+    /// True for the arithmetic operators whose result may exceed the range of its integer type
+    pub fn may_overflow(&self) -> bool {
+        matches!(self, BinaryOpKind::Add | BinaryOpKind::Subtract | BinaryOpKind::Multiply)
+    }
+
+    /// True for the bit shift operators `<<` and `>>`
+    pub fn is_shift(&self) -> bool {
+        matches!(self, BinaryOpKind::ShiftLeft | BinaryOpKind::ShiftRight)
+    }
+
+    /// The name of the struct method which implements this operator for struct operands,
+    /// e.g. `a + b` calls `a.add(b)`. Comparators cannot be implemented by a method.
+    pub fn method_name(&self) -> Option<&'static str> {
+        match self {
+            BinaryOpKind::Add => Some("add"),
+            BinaryOpKind::Subtract => Some("sub"),
+            BinaryOpKind::Multiply => Some("mul"),
+            BinaryOpKind::Divide => Some("div"),
+            BinaryOpKind::Modulo => Some("rem"),
+            BinaryOpKind::And => Some("and"),
+            BinaryOpKind::Or => Some("or"),
+            BinaryOpKind::Xor => Some("xor"),
+            BinaryOpKind::ShiftLeft => Some("shl"),
+            BinaryOpKind::ShiftRight => Some("shr"),
+            BinaryOpKind::Equal
+            | BinaryOpKind::NotEqual
+            | BinaryOpKind::Less
+            | BinaryOpKind::LessEqual
+            | BinaryOpKind::Greater
+            | BinaryOpKind::GreaterEqual => None,
+        }
+    }
+
     pub fn as_string(self) -> &'static str {
         match self {
             BinaryOpKind::Add => "+",
@@ -269,6 +345,8 @@ pub enum Literal {
     Array(ArrayLiteral),
     Bool(bool),
     Integer(FieldElement),
+    /// An integer literal written with the `_Field` suffix
+    Field(FieldElement),
     Str(String),
 }
 
@@ -321,6 +399,8 @@ pub struct FunctionDefinition {
 
     pub generics: UnresolvedGenerics,
     pub parameters: Vec<(Pattern, UnresolvedType, noirc_abi::AbiVisibility)>,
+    /// The default value of each parameter in `parameters`, if it has one.
+    pub parameter_defaults: Vec<Option<Expression>>,
     pub body: BlockExpression,
     pub span: Span,
     pub return_type: UnresolvedType,
@@ -351,6 +431,8 @@ pub enum ArrayLiteral {
 pub struct CallExpression {
     pub func: Box<Expression>,
     pub arguments: Vec<Expression>,
+    /// The name given to each argument, if the call uses named arguments.
+    pub argument_names: Option<Vec<Ident>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -378,6 +460,14 @@ pub struct IndexExpression {
     pub index: Expression, // XXX: We accept two types of indices, either a normal integer or a constant
 }
 
+/// A sub-array, as in `array[start..end]`. The end is exclusive.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SliceExpression {
+    pub collection: Expression,
+    pub start: Expression,
+    pub end: Expression,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BlockExpression(pub Vec<Statement>);
 
@@ -409,11 +499,13 @@ impl Display for ExpressionKind {
             Block(block) => block.fmt(f),
             Prefix(prefix) => prefix.fmt(f),
             Index(index) => index.fmt(f),
+            Slice(slice) => slice.fmt(f),
             Call(call) => call.fmt(f),
             MethodCall(call) => call.fmt(f),
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             For(for_loop) => for_loop.fmt(f),
+            While(while_loop) => while_loop.fmt(f),
             If(if_expr) => if_expr.fmt(f),
             Variable(path) => path.fmt(f),
             Constructor(constructor) => constructor.fmt(f),
@@ -440,6 +532,7 @@ impl Display for Literal {
             }
             Literal::Bool(boolean) => write!(f, "{}", if *boolean { "true" } else { "false" }),
             Literal::Integer(integer) => write!(f, "{}", integer.to_u128()),
+            Literal::Field(integer) => write!(f, "{}_Field", integer.to_u128()),
             Literal::Str(string) => write!(f, "\"{string}\""),
         }
     }
@@ -479,9 +572,20 @@ impl Display for IndexExpression {
     }
 }
 
+impl Display for SliceExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}..{}]", self.collection, self.start, self.end)
+    }
+}
+
 impl Display for CallExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let args = vecmap(&self.arguments, ToString::to_string);
+        let args = match &self.argument_names {
+            Some(names) => {
+                vecmap(names.iter().zip(&self.arguments), |(name, arg)| format!("{name}: {arg}"))
+            }
+            None => vecmap(&self.arguments, ToString::to_string),
+        };
         write!(f, "{}({})", self.func, args.join(", "))
     }
 }
@@ -553,6 +657,12 @@ impl Display for ForExpression {
     }
 }
 
+impl Display for WhileExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "while {} {}", self.condition, self.block)
+    }
+}
+
 impl Display for IfExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "if {} {}", self.condition, self.consequence)?;
@@ -577,9 +687,14 @@ impl Display for FunctionDefinition {
             writeln!(f, "{attribute}")?;
         }
 
-        let parameters = vecmap(&self.parameters, |(name, r#type, visibility)| {
-            format!("{name}: {visibility} {type}")
-        });
+        let parameters =
+            vecmap(self.parameters.iter().zip(&self.parameter_defaults), |(parameter, default)| {
+                let (name, r#type, visibility) = parameter;
+                match default {
+                    Some(default) => format!("{name}: {visibility} {type} = {default}"),
+                    None => format!("{name}: {visibility} {type}"),
+                }
+            });
 
         write!(
             f,
@@ -25,6 +25,8 @@ pub enum Statement {
     Assign(AssignStatement),
     // This is an expression with a trailing semi-colon
     Semi(Expression),
+    // `return` with an optional value, leaving the enclosing function early
+    Return(Option<Expression>),
     // This statement is the result of a recovered parse error.
     // To avoid issuing multiple errors in later steps, it should
     // be skipped in any future analysis if possible.
@@ -56,6 +58,7 @@ impl Statement {
             | Statement::Constrain(_)
             | Statement::Assign(_)
             | Statement::Semi(_)
+            | Statement::Return(_)
             | Statement::Error => {
                 // To match rust, statements always require a semicolon, even at the end of a block
                 if semi.is_none() {
@@ -72,6 +75,7 @@ impl Statement {
                     // Semicolons are optional for these expressions
                     (ExpressionKind::Block(_), semi, _)
                     | (ExpressionKind::For(_), semi, _)
+                    | (ExpressionKind::While(_), semi, _)
                     | (ExpressionKind::If(_), semi, _) => {
                         if semi.is_some() {
                             Statement::Semi(expr)
@@ -345,8 +349,16 @@ pub enum LValue {
     Index { array: Box<LValue>, index: Expression },
 }
 
+/// An `assert(condition)` or `assert(condition, message)` statement.
+/// The deprecated `constrain condition` form never has a message.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct ConstrainStatement(pub Expression);
+pub struct ConstrainStatement(pub Expression, pub Option<Expression>);
+
+impl Recoverable for ConstrainStatement {
+    fn error(span: Span) -> Self {
+        ConstrainStatement(Expression::error(span), None)
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Pattern {
@@ -398,6 +410,8 @@ impl Display for Statement {
             Statement::Expression(expression) => expression.fmt(f),
             Statement::Assign(assign) => assign.fmt(f),
             Statement::Semi(semi) => write!(f, "{semi};"),
+            Statement::Return(Some(value)) => write!(f, "return {value}"),
+            Statement::Return(None) => write!(f, "return"),
             Statement::Error => write!(f, "Error"),
         }
     }
@@ -411,7 +425,11 @@ impl Display for LetStatement {
 
 impl Display for ConstrainStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "constrain {}", self.0)
+        write!(f, "constrain {}", self.0)?;
+        if let Some(message) = &self.1 {
+            write!(f, ", {message}")?;
+        }
+        Ok(())
     }
 }
 
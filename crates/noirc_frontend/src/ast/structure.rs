@@ -24,6 +24,14 @@ impl NoirStruct {
     }
 }
 
+/// Ast node for a type alias, e.g. `type Word = u32;`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirTypeAlias {
+    pub name: Ident,
+    pub typ: UnresolvedType,
+    pub span: Span,
+}
+
 /// Ast node for an impl
 #[derive(Clone, Debug)]
 pub struct NoirImpl {
@@ -48,6 +56,12 @@ impl Display for NoirStruct {
     }
 }
 
+impl Display for NoirTypeAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "type {} = {};", self.name, self.typ)
+    }
+}
+
 impl Display for NoirImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let generics = vecmap(&self.generics, |generic| generic.to_string());
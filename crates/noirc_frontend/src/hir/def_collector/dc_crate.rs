@@ -1,6 +1,6 @@
 use super::dc_mod::collect_defs;
 use super::errors::DefCollectorErrorKind;
-use crate::graph::{CrateId, LOCAL_CRATE};
+use crate::graph::{CrateId, CrateType, LOCAL_CRATE};
 use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
 use crate::hir::resolution::errors::ResolverError;
 use crate::hir::resolution::resolver::Resolver;
@@ -8,18 +8,18 @@ use crate::hir::resolution::{
     import::{resolve_imports, ImportDirective},
     path_resolver::StandardPathResolver,
 };
-use crate::hir::type_check::{type_check_func, TypeChecker};
+use crate::hir::type_check::{type_check_func, type_check_main, TypeChecker};
 use crate::hir::Context;
-use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId};
+use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId, TypeAliasId};
 use crate::{
-    ExpressionKind, Generics, Ident, LetStatement, NoirFunction, NoirStruct, ParsedModule, Shared,
-    Type, TypeBinding, UnresolvedGenerics, UnresolvedType,
+    ExpressionKind, Generics, Ident, LetStatement, NoirFunction, NoirStruct, NoirTypeAlias,
+    ParsedModule, Shared, Type, TypeBinding, UnresolvedGenerics, UnresolvedType,
 };
 use fm::FileId;
 use iter_extended::vecmap;
 use noirc_errors::Span;
 use noirc_errors::{CustomDiagnostic, FileDiagnostic};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
 
 /// Stores all of the unresolved functions in a particular file/mod
@@ -40,6 +40,13 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedTypeAlias {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub type_alias_id: TypeAliasId,
+    pub type_alias_def: NoirTypeAlias,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedGlobal {
     pub file_id: FileId,
@@ -54,6 +61,7 @@ pub struct DefCollector {
     pub(crate) collected_imports: Vec<ImportDirective>,
     pub(crate) collected_functions: Vec<UnresolvedFunctions>,
     pub(crate) collected_types: HashMap<StructId, UnresolvedStruct>,
+    pub(crate) collected_type_aliases: Vec<UnresolvedTypeAlias>,
     pub(crate) collected_globals: Vec<UnresolvedGlobal>,
     pub(crate) collected_impls: ImplMap,
 }
@@ -71,6 +79,7 @@ impl DefCollector {
             collected_imports: vec![],
             collected_functions: vec![],
             collected_types: HashMap::new(),
+            collected_type_aliases: vec![],
             collected_impls: HashMap::new(),
             collected_globals: vec![],
         }
@@ -158,7 +167,13 @@ impl DefCollector {
         let mut file_global_ids = resolve_globals(context, integer_globals, crate_id, errors);
 
         // Must resolve structs before we resolve globals.
-        resolve_structs(context, def_collector.collected_types, crate_id, errors);
+        resolve_structs(
+            context,
+            def_collector.collected_types,
+            def_collector.collected_type_aliases,
+            crate_id,
+            errors,
+        );
 
         // We must wait to resolve non-integer globals until after we resolve structs since structs
         // globals will need to reference the struct type they're initialized to to ensure they are valid.
@@ -195,6 +210,15 @@ impl DefCollector {
         // Type check all of the functions in the crate
         type_check_functions(&mut context.def_interner, file_func_ids, errors);
         type_check_functions(&mut context.def_interner, file_method_ids, errors);
+
+        // The main function of a binary crate is the entry point of the circuit
+        // and must additionally have a signature that can be expressed in the ABI
+        if context.crate_graph[crate_id].crate_type == CrateType::Binary {
+            if let Some(main) = context.def_maps[&crate_id].main_function() {
+                let file = context.def_interner.function_meta(&main).location.file;
+                extend_errors(errors, file, type_check_main(&context.def_interner, main));
+            }
+        }
     }
 }
 
@@ -311,8 +335,9 @@ fn type_check_globals(
     all_errors: &mut Vec<FileDiagnostic>,
 ) {
     for (file_id, stmt_id) in global_ids {
-        let errors = TypeChecker::check_global(&stmt_id, interner);
-        extend_errors(all_errors, file_id, errors);
+        let output = TypeChecker::check_global(&stmt_id, interner);
+        extend_errors(all_errors, file_id, output.errors);
+        extend_errors(all_errors, file_id, output.warnings);
     }
 }
 
@@ -321,6 +346,7 @@ fn type_check_globals(
 fn resolve_structs(
     context: &mut Context,
     structs: HashMap<StructId, UnresolvedStruct>,
+    type_aliases: Vec<UnresolvedTypeAlias>,
     crate_id: CrateId,
     errors: &mut Vec<FileDiagnostic>,
 ) {
@@ -332,6 +358,10 @@ fn resolve_structs(
         context.def_interner.push_empty_struct(*type_id, typ);
     }
 
+    // Type aliases may name structs and struct fields may name type aliases, so the aliases
+    // are resolved once every struct id is known but before any struct fields are resolved.
+    resolve_type_aliases(context, type_aliases, crate_id, errors);
+
     for (type_id, typ) in structs {
         let (generics, fields) = resolve_struct_fields(context, crate_id, typ, errors);
         context.def_interner.update_struct(type_id, |struct_def| {
@@ -360,6 +390,93 @@ fn resolve_struct_fields(
     (generics, fields)
 }
 
+/// Aliases may refer to aliases declared after them, so they are resolved in passes. An alias
+/// naming another which is not resolved yet is retried in the next pass.
+fn resolve_type_aliases(
+    context: &mut Context,
+    mut type_aliases: Vec<UnresolvedTypeAlias>,
+    crate_id: CrateId,
+    errors: &mut Vec<FileDiagnostic>,
+) {
+    while !type_aliases.is_empty() {
+        let alias_count = type_aliases.len();
+        let mut deferred = Vec::new();
+
+        // The unresolved aliases named by each deferred alias
+        let mut blocked_on = HashMap::new();
+
+        for alias in type_aliases {
+            let (typ, alias_errors) = resolve_type_alias(context, crate_id, &alias);
+            let dependencies: Vec<_> = alias_errors
+                .iter()
+                .filter_map(|error| match error {
+                    ResolverError::TypeAliasUsedBeforeDefinition { id, .. } => Some(*id),
+                    _ => None,
+                })
+                .collect();
+
+            if dependencies.is_empty() {
+                extend_errors(errors, alias.file_id, alias_errors);
+                context.def_interner.update_type_alias(alias.type_alias_id, typ);
+            } else {
+                blocked_on.insert(alias.type_alias_id, dependencies);
+                deferred.push(alias);
+            }
+        }
+
+        // If a pass resolves nothing, every alias left waits on another, so some of them must
+        // form a cycle. Those are reported and resolved to an error, which unblocks the rest.
+        if deferred.len() == alias_count {
+            for alias in &deferred {
+                if depends_on_itself(alias.type_alias_id, &blocked_on) {
+                    let name = alias.type_alias_def.name.0.contents.clone();
+                    let span = alias.type_alias_def.name.span();
+                    let error = ResolverError::CyclicTypeAlias { name, span };
+                    errors.push(error.into_file_diagnostic(alias.file_id));
+                    context.def_interner.update_type_alias(alias.type_alias_id, Type::Error);
+                }
+            }
+            deferred
+                .retain(|alias| context.def_interner.get_type_alias(alias.type_alias_id).is_none());
+        }
+
+        type_aliases = deferred;
+    }
+}
+
+/// True if following the aliases each alias is waiting on leads back to the given alias
+fn depends_on_itself(
+    alias: TypeAliasId,
+    blocked_on: &HashMap<TypeAliasId, Vec<TypeAliasId>>,
+) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = blocked_on.get(&alias).cloned().unwrap_or_default();
+
+    while let Some(next) = stack.pop() {
+        if next == alias {
+            return true;
+        }
+        if visited.insert(next) {
+            stack.extend(blocked_on.get(&next).into_iter().flatten());
+        }
+    }
+    false
+}
+
+fn resolve_type_alias(
+    context: &mut Context,
+    krate: CrateId,
+    unresolved: &UnresolvedTypeAlias,
+) -> (Type, Vec<ResolverError>) {
+    let path_resolver =
+        StandardPathResolver::new(ModuleId { local_id: unresolved.module_id, krate });
+
+    let file = unresolved.file_id;
+
+    Resolver::new(&mut context.def_interner, &path_resolver, &context.def_maps, file)
+        .resolve_type_alias(unresolved.type_alias_def.clone())
+}
+
 fn resolve_impls(
     interner: &mut NodeInterner,
     crate_id: CrateId,
@@ -476,6 +593,8 @@ fn type_check_functions(
     errors: &mut Vec<FileDiagnostic>,
 ) {
     for (file, func) in file_func_ids {
-        extend_errors(errors, file, type_check_func(interner, func));
+        let output = type_check_func(interner, func);
+        extend_errors(errors, file, output.errors);
+        extend_errors(errors, file, output.warnings);
     }
 }
@@ -3,11 +3,12 @@ use noirc_errors::FileDiagnostic;
 
 use crate::{
     graph::CrateId, hir::def_collector::dc_crate::UnresolvedStruct, node_interner::StructId,
-    parser::SubModule, Ident, LetStatement, NoirFunction, NoirImpl, NoirStruct, ParsedModule,
+    parser::SubModule, Ident, LetStatement, NoirFunction, NoirImpl, NoirStruct, NoirTypeAlias,
+    ParsedModule,
 };
 
 use super::{
-    dc_crate::{DefCollector, UnresolvedFunctions, UnresolvedGlobal},
+    dc_crate::{DefCollector, UnresolvedFunctions, UnresolvedGlobal, UnresolvedTypeAlias},
     errors::DefCollectorErrorKind,
 };
 use crate::hir::def_map::{parse_file, LocalModuleId, ModuleData, ModuleId, ModuleOrigin};
@@ -55,6 +56,8 @@ pub fn collect_defs(
 
     collector.collect_structs(ast.types, crate_id, errors);
 
+    collector.collect_type_aliases(context, ast.type_aliases, errors);
+
     collector.collect_functions(context, ast.functions, errors);
 
     collector.collect_impls(context, ast.impls);
@@ -183,6 +186,35 @@ impl<'a> ModCollector<'a> {
         }
     }
 
+    /// Collect any type aliases declared within the ast.
+    fn collect_type_aliases(
+        &mut self,
+        context: &mut Context,
+        type_aliases: Vec<NoirTypeAlias>,
+        errors: &mut Vec<FileDiagnostic>,
+    ) {
+        for type_alias in type_aliases {
+            let name = type_alias.name.clone();
+            let type_alias_id = context.def_interner.push_empty_type_alias();
+
+            // Add the type alias to scope so its path can be looked up later
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .declare_type_alias(name, type_alias_id);
+
+            if let Err((first_def, second_def)) = result {
+                let err = DefCollectorErrorKind::DuplicateFunction { first_def, second_def };
+                errors.push(err.into_file_diagnostic(self.file_id));
+            }
+
+            self.def_collector.collected_type_aliases.push(UnresolvedTypeAlias {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                type_alias_id,
+                type_alias_def: type_alias,
+            });
+        }
+    }
+
     fn collect_submodules(
         &mut self,
         context: &mut Context,
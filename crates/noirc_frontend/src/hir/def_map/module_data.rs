@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use fm::FileId;
 
 use crate::{
-    node_interner::{FuncId, StmtId, StructId},
+    node_interner::{FuncId, StmtId, StructId, TypeAliasId},
     Ident,
 };
 
@@ -65,6 +65,14 @@ impl ModuleData {
         self.declare(name, ModuleDefId::TypeId(id))
     }
 
+    pub fn declare_type_alias(
+        &mut self,
+        name: Ident,
+        id: TypeAliasId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.declare(name, ModuleDefId::TypeAliasId(id))
+    }
+
     pub fn declare_child_module(
         &mut self,
         name: Ident,
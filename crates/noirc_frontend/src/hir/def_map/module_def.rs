@@ -1,13 +1,14 @@
-use crate::node_interner::{FuncId, StmtId, StructId};
+use crate::node_interner::{FuncId, StmtId, StructId, TypeAliasId};
 
 use super::ModuleId;
 
-/// A generic ID that references either a module, function, type, or global
+/// A generic ID that references either a module, function, type, type alias, or global
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ModuleDefId {
     ModuleId(ModuleId),
     FunctionId(FuncId),
     TypeId(StructId),
+    TypeAliasId(TypeAliasId),
     GlobalId(StmtId),
 }
 
@@ -26,6 +27,13 @@ impl ModuleDefId {
         }
     }
 
+    pub fn as_type_alias(&self) -> Option<TypeAliasId> {
+        match self {
+            ModuleDefId::TypeAliasId(alias_id) => Some(*alias_id),
+            _ => None,
+        }
+    }
+
     pub fn as_global(&self) -> Option<StmtId> {
         match self {
             ModuleDefId::GlobalId(stmt_id) => Some(*stmt_id),
@@ -39,6 +47,7 @@ impl ModuleDefId {
         match self {
             ModuleDefId::FunctionId(_) => "function",
             ModuleDefId::TypeId(_) => "type",
+            ModuleDefId::TypeAliasId(_) => "type alias",
             ModuleDefId::ModuleId(_) => "module",
             ModuleDefId::GlobalId(_) => "global",
         }
@@ -97,6 +106,20 @@ impl TryFromModuleDefId for StructId {
     }
 }
 
+impl TryFromModuleDefId for TypeAliasId {
+    fn try_from(id: ModuleDefId) -> Option<Self> {
+        id.as_type_alias()
+    }
+
+    fn dummy_id() -> Self {
+        TypeAliasId::dummy_id()
+    }
+
+    fn description() -> String {
+        "type alias".to_string()
+    }
+}
+
 impl TryFromModuleDefId for StmtId {
     fn try_from(id: ModuleDefId) -> Option<Self> {
         id.as_global()
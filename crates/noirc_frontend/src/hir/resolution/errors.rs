@@ -2,7 +2,7 @@ pub use noirc_errors::Span;
 use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
 use thiserror::Error;
 
-use crate::{parser::ParserError, Ident, Type};
+use crate::{node_interner::TypeAliasId, parser::ParserError, Ident, Type};
 
 use super::import::PathResolutionError;
 
@@ -42,6 +42,8 @@ pub enum ResolverError {
     InvalidArrayLengthExpr { span: Span },
     #[error("Integer too large to be evaluated in an array length context")]
     IntegerTooLarge { span: Span },
+    #[error("Array-length expression does not evaluate to a valid length")]
+    InvalidArrayLengthArithmetic { span: Span },
     #[error("No global or generic type parameter found with the given name")]
     NoSuchNumericTypeVariable { path: crate::Path },
     #[error("Closures cannot capture mutable variables")]
@@ -54,12 +56,22 @@ pub enum ResolverError {
     NonStructWithGenerics { span: Span },
     #[error("Cannot apply generics on Self type")]
     GenericsOnSelfType { span: Span },
+    #[error("Type aliases cannot be generic")]
+    GenericTypeAlias { span: Span },
+    #[error("Type alias used before it was resolved")]
+    TypeAliasUsedBeforeDefinition { id: TypeAliasId, name: String, span: Span },
+    #[error("Type alias depends on itself")]
+    CyclicTypeAlias { name: String, span: Span },
     #[error("Incorrect amount of arguments to generic type constructor")]
     IncorrectGenericCount { span: Span, struct_type: String, actual: usize, expected: usize },
     #[error("{0}")]
     ParserError(ParserError),
     #[error("Function is not defined in a contract yet sets its contract visibility")]
     ContractFunctionTypeInNormalFunction { span: Span },
+    #[error("Parameter default is not a simple expression")]
+    UnsupportedParameterDefault { span: Span },
+    #[error("Required parameter follows a parameter with a default value")]
+    RequiredParameterAfterDefault { span: Span },
 }
 
 impl ResolverError {
@@ -212,6 +224,11 @@ impl From<ResolverError> for Diagnostic {
                 "Array-lengths may be a maximum size of usize::MAX, including intermediate calculations".into(),
                 span,
             ),
+            ResolverError::InvalidArrayLengthArithmetic { span } => Diagnostic::simple_error(
+                "Array-length expression does not evaluate to a valid length".into(),
+                "Arithmetic in an array-length must not overflow, go below zero, or divide by zero".into(),
+                span,
+            ),
             ResolverError::NoSuchNumericTypeVariable { path } => Diagnostic::simple_error(
                 format!("Cannot find a global or generic type parameter named `{path}`"),
                 "Only globals or generic type parameters are allowed to be used as an array type's length".to_string(),
@@ -242,6 +259,21 @@ impl From<ResolverError> for Diagnostic {
                 "Use an explicit type name or apply the generics at the start of the impl instead".into(),
                 span,
             ),
+            ResolverError::GenericTypeAlias { span } => Diagnostic::simple_error(
+                "Type aliases cannot be generic".into(),
+                "Give every array and string in the alias an explicit length".into(),
+                span,
+            ),
+            ResolverError::TypeAliasUsedBeforeDefinition { name, span, .. } => Diagnostic::simple_error(
+                format!("The type alias {name} is used before it is defined"),
+                "Globals initialized with a literal are resolved before any type alias".into(),
+                span,
+            ),
+            ResolverError::CyclicTypeAlias { name, span } => Diagnostic::simple_error(
+                format!("The type alias {name} depends on itself"),
+                "Type aliases may not refer to themselves, directly or through other aliases".into(),
+                span,
+            ),
             ResolverError::IncorrectGenericCount { span, struct_type, actual, expected } => {
                 let expected_plural = if expected == 1 { "" } else { "s" };
                 let actual_plural = if actual == 1 { "is" } else { "are" };
@@ -258,6 +290,16 @@ impl From<ResolverError> for Diagnostic {
                 "Non-contract functions cannot be 'open'".into(),
                 span,
             ),
+            ResolverError::UnsupportedParameterDefault { span } => Diagnostic::simple_error(
+                "Default parameter values may not contain blocks, calls, or other control flow".into(),
+                "Only literals, variables, and operators, casts, arrays and tuples of them are allowed".into(),
+                span,
+            ),
+            ResolverError::RequiredParameterAfterDefault { span } => Diagnostic::simple_error(
+                "Parameters with a default value must come after every required parameter".into(),
+                "This default is followed by a parameter without one".into(),
+                span,
+            ),
         }
     }
 }
@@ -152,6 +152,9 @@ fn resolve_name_in_module(
             ModuleDefId::FunctionId(_) => panic!("functions cannot be in the type namespace"),
             // TODO: If impls are ever implemented, types can be used in a path
             ModuleDefId::TypeId(id) => id.0,
+            ModuleDefId::TypeAliasId(_) => {
+                return Err(PathResolutionError::Unresolved(segment.clone()))
+            }
             ModuleDefId::GlobalId(_) => panic!("globals cannot be in the type namespace"),
         };
 
@@ -15,7 +15,7 @@ use crate::hir_def::expr::{
     HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCastExpression,
     HirConstructorExpression, HirExpression, HirForExpression, HirIdent, HirIfExpression,
     HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral, HirMemberAccess,
-    HirMethodCallExpression, HirPrefixExpression,
+    HirMethodCallExpression, HirPrefixExpression, HirSliceExpression, HirWhileExpression,
 };
 use crate::token::Attribute;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -33,8 +33,8 @@ use crate::{
     Statement,
 };
 use crate::{
-    ArrayLiteral, ContractFunctionType, Generics, LValue, NoirStruct, Path, Pattern, Shared,
-    StructType, Type, TypeBinding, TypeVariable, UnresolvedGenerics, UnresolvedType,
+    ArrayLiteral, ContractFunctionType, Generics, LValue, NoirStruct, NoirTypeAlias, Path, Pattern,
+    Shared, StructType, Type, TypeBinding, TypeVariable, UnresolvedGenerics, UnresolvedType,
     UnresolvedTypeExpression, ERROR_IDENT,
 };
 use fm::FileId;
@@ -396,6 +396,19 @@ impl<'a> Resolver<'a> {
         }
 
         let span = path.span();
+        if let Ok(ModuleDefId::TypeAliasId(id)) =
+            self.path_resolver.resolve(self.def_maps, path.clone())
+        {
+            if !args.is_empty() {
+                self.push_err(ResolverError::NonStructWithGenerics { span });
+            }
+            return self.interner.get_type_alias(id).unwrap_or_else(|| {
+                let name = path.to_string();
+                self.push_err(ResolverError::TypeAliasUsedBeforeDefinition { id, name, span });
+                Type::Error
+            });
+        }
+
         match self.lookup_struct_or_error(path) {
             Some(struct_type) => {
                 let mut args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
@@ -465,21 +478,23 @@ impl<'a> Resolver<'a> {
                 })
             }
             UnresolvedTypeExpression::Constant(int, _) => Type::Constant(int),
-            UnresolvedTypeExpression::BinaryOperation(lhs, op, rhs, _) => {
-                let (lhs_span, rhs_span) = (lhs.span(), rhs.span());
+            UnresolvedTypeExpression::BinaryOperation(lhs, op, rhs, span) => {
                 let lhs = self.convert_expression_type(*lhs);
                 let rhs = self.convert_expression_type(*rhs);
 
                 match (lhs, rhs) {
                     (Type::Constant(lhs), Type::Constant(rhs)) => {
-                        Type::Constant(op.function()(lhs, rhs))
-                    }
-                    (lhs, _) => {
-                        let span =
-                            if !matches!(lhs, Type::Constant(_)) { lhs_span } else { rhs_span };
-                        self.push_err(ResolverError::InvalidArrayLengthExpr { span });
-                        Type::Constant(0)
+                        // A length of zero is allowed, as it is when written directly
+                        match op.checked_function()(lhs, rhs) {
+                            Some(length) => Type::Constant(length),
+                            None => {
+                                self.push_err(ResolverError::InvalidArrayLengthArithmetic { span });
+                                Type::Constant(0)
+                            }
+                        }
                     }
+                    // Expressions over generics are folded once the generics are known
+                    (lhs, rhs) => Type::InfixExpr(Box::new(lhs), op, Box::new(rhs)),
                 }
             }
         }
@@ -582,6 +597,23 @@ impl<'a> Resolver<'a> {
         (generics, fields, self.errors)
     }
 
+    /// Resolves the type a type alias stands for. Aliases are expanded in place during
+    /// name resolution, so the type checker only ever sees the underlying type.
+    pub fn resolve_type_alias(mut self, unresolved: NoirTypeAlias) -> (Type, Vec<ResolverError>) {
+        self.resolve_local_globals();
+
+        let mut new_variables = vec![];
+        let typ = self.resolve_type_inner(unresolved.typ, &mut new_variables);
+
+        // Every use of the alias shares the resolved type, so it must not contain any
+        // implicit type variables such as the length of an unsized array.
+        if !new_variables.is_empty() {
+            self.push_err(ResolverError::GenericTypeAlias { span: unresolved.span });
+        }
+
+        (typ, self.errors)
+    }
+
     fn resolve_local_globals(&mut self) {
         for (stmt_id, global_info) in self.interner.get_all_globals() {
             if global_info.local_id == self.path_resolver.local_module_id() {
@@ -611,6 +643,13 @@ impl<'a> Resolver<'a> {
                 }
             });
 
+        // Default values are resolved before the parameters are in scope since they are
+        // evaluated at each call site rather than within the function itself.
+        let parameter_defaults = vecmap(&func.def.parameter_defaults, |default| {
+            default.clone().map(|default| self.resolve_parameter_default(default))
+        });
+        self.check_parameter_default_order(&func.def.parameter_defaults);
+
         let mut parameters = vec![];
         let mut parameter_types = vec![];
 
@@ -666,12 +705,59 @@ impl<'a> Resolver<'a> {
             location,
             typ,
             parameters: parameters.into(),
+            parameter_defaults,
             return_visibility: func.def.return_visibility,
             return_distinctness: func.def.return_distinctness,
             has_body: !func.def.body.is_empty(),
         }
     }
 
+    /// Each call which leaves out a parameter is given its own copy of the default value, so a
+    /// default is limited to the expressions the interner can copy: literals, variables, and
+    /// the operators, casts, arrays and tuples built from them.
+    fn resolve_parameter_default(&mut self, default: Expression) -> ExprId {
+        if Self::is_copyable_default(&default) {
+            return self.resolve_expression(default);
+        }
+
+        self.push_err(ResolverError::UnsupportedParameterDefault { span: default.span });
+        let expr_id = self.interner.push_expr(HirExpression::Error);
+        self.interner.push_expr_location(expr_id, default.span, self.file);
+        expr_id
+    }
+
+    fn is_copyable_default(default: &Expression) -> bool {
+        match &default.kind {
+            ExpressionKind::Literal(Literal::Array(ArrayLiteral::Standard(elements)))
+            | ExpressionKind::Tuple(elements) => elements.iter().all(Self::is_copyable_default),
+            ExpressionKind::Literal(Literal::Array(ArrayLiteral::Repeated {
+                repeated_element,
+                ..
+            })) => Self::is_copyable_default(repeated_element),
+            ExpressionKind::Literal(_) | ExpressionKind::Variable(_) | ExpressionKind::Error => {
+                true
+            }
+            ExpressionKind::Prefix(prefix) => Self::is_copyable_default(&prefix.rhs),
+            ExpressionKind::Infix(infix) => {
+                Self::is_copyable_default(&infix.lhs) && Self::is_copyable_default(&infix.rhs)
+            }
+            ExpressionKind::Cast(cast) => Self::is_copyable_default(&cast.lhs),
+            _ => false,
+        }
+    }
+
+    /// A required parameter after one with a default value could never be given positionally
+    /// without also giving the default, so every parameter after a default must have one too.
+    fn check_parameter_default_order(&mut self, defaults: &[Option<Expression>]) {
+        let first_default = defaults.iter().position(Option::is_some);
+        if let Some(index) = first_default {
+            if defaults[index..].contains(&None) {
+                let span = defaults[index].as_ref().unwrap().span;
+                self.push_err(ResolverError::RequiredParameterAfterDefault { span });
+            }
+        }
+    }
+
     /// True if the 'pub' keyword is allowed on parameters in this function
     fn pub_allowed(&self, func: &NoirFunction) -> bool {
         if self.in_contract() {
@@ -746,6 +832,7 @@ impl<'a> Resolver<'a> {
             Type::FieldElement(_)
             | Type::Integer(_, _, _)
             | Type::Bool(_)
+            | Type::Char
             | Type::String(_)
             | Type::Unit
             | Type::Error
@@ -753,13 +840,10 @@ impl<'a> Resolver<'a> {
             | Type::PolymorphicInteger(_, _)
             | Type::Constant(_)
             | Type::NamedGeneric(_, _)
+            | Type::InfixExpr(..)
             | Type::Forall(_, _) => (),
 
-            Type::Array(length, _) => {
-                if let Type::NamedGeneric(type_variable, name) = length.as_ref() {
-                    found.insert(name.to_string(), type_variable.clone());
-                }
-            }
+            Type::Array(length, _) => Self::find_numeric_generics_in_length(length, found),
 
             Type::Tuple(fields) => {
                 for field in fields {
@@ -787,6 +871,22 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    fn find_numeric_generics_in_length(
+        length: &Type,
+        found: &mut HashMap<String, Shared<TypeBinding>>,
+    ) {
+        match length {
+            Type::NamedGeneric(type_variable, name) => {
+                found.insert(name.to_string(), type_variable.clone());
+            }
+            Type::InfixExpr(lhs, _, rhs) => {
+                Self::find_numeric_generics_in_length(lhs, found);
+                Self::find_numeric_generics_in_length(rhs, found);
+            }
+            _ => (),
+        }
+    }
+
     pub fn resolve_global_let(&mut self, let_stmt: crate::LetStatement) -> HirStatement {
         let expression = self.resolve_expression(let_stmt.expression);
         let definition = DefinitionKind::Global(expression);
@@ -811,10 +911,14 @@ impl<'a> Resolver<'a> {
             }
             Statement::Constrain(constrain_stmt) => {
                 let expr_id = self.resolve_expression(constrain_stmt.0);
-                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file))
+                let message = constrain_stmt.1.map(|message| self.resolve_expression(message));
+                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file, message))
             }
             Statement::Expression(expr) => HirStatement::Expression(self.resolve_expression(expr)),
             Statement::Semi(expr) => HirStatement::Semi(self.resolve_expression(expr)),
+            Statement::Return(value) => {
+                HirStatement::Return(value.map(|value| self.resolve_expression(value)))
+            }
             Statement::Assign(assign_stmt) => {
                 let identifier = self.resolve_lvalue(assign_stmt.lvalue);
                 let expression = self.resolve_expression(assign_stmt.expression);
@@ -870,6 +974,7 @@ impl<'a> Resolver<'a> {
                     HirLiteral::Array(HirArrayLiteral::Repeated { repeated_element, length })
                 }
                 Literal::Integer(integer) => HirLiteral::Integer(integer),
+                Literal::Field(integer) => HirLiteral::Field(integer),
                 Literal::Str(str) => HirLiteral::Str(str),
             }),
             ExpressionKind::Variable(path) => {
@@ -899,8 +1004,9 @@ impl<'a> Resolver<'a> {
                 // Get the span and name of path for error reporting
                 let func = self.resolve_expression(*call_expr.func);
                 let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                let argument_names = call_expr.argument_names;
                 let location = Location::new(expr.span, self.file);
-                HirExpression::Call(HirCallExpression { func, arguments, location })
+                HirExpression::Call(HirCallExpression { func, arguments, argument_names, location })
             }
             ExpressionKind::MethodCall(call_expr) => {
                 let method = call_expr.method_name;
@@ -942,6 +1048,10 @@ impl<'a> Resolver<'a> {
                     identifier,
                 })
             }
+            ExpressionKind::While(while_expr) => HirExpression::While(HirWhileExpression {
+                condition: self.resolve_expression(while_expr.condition),
+                block: self.resolve_expression(while_expr.block),
+            }),
             ExpressionKind::If(if_expr) => HirExpression::If(HirIfExpression {
                 condition: self.resolve_expression(if_expr.condition),
                 consequence: self.resolve_expression(if_expr.consequence),
@@ -951,6 +1061,11 @@ impl<'a> Resolver<'a> {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
             }),
+            ExpressionKind::Slice(slice) => HirExpression::Slice(HirSliceExpression {
+                collection: self.resolve_expression(slice.collection),
+                start: self.resolve_expression(slice.start),
+                end: self.resolve_expression(slice.end),
+            }),
             ExpressionKind::Block(block_expr) => self.resolve_block(block_expr),
             ExpressionKind::Constructor(constructor) => {
                 let span = constructor.type_name.span();
@@ -1259,7 +1374,11 @@ impl<'a> Resolver<'a> {
             HirExpression::Literal(HirLiteral::Integer(int)) => {
                 int.try_into_u128().ok_or(Some(ResolverError::IntegerTooLarge { span }))
             }
-            _other => Err(Some(ResolverError::InvalidArrayLengthExpr { span })),
+            // Globals such as `global N = 2 * 4;` may also be folded arithmetic on literals
+            _ => self
+                .interner
+                .constant_value(rhs)
+                .ok_or(Some(ResolverError::InvalidArrayLengthExpr { span })),
         }
     }
 
@@ -1505,6 +1624,57 @@ mod test {
         let errors = resolve_src_code(src, vec!["main", "foo"]);
         assert!(errors.is_empty());
     }
+    #[test]
+    fn resolve_repeated_array_with_runtime_length() {
+        let src = r#"
+            fn main(n : Field) {
+                assert(n != 0);
+                let _a = [0; n];
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ResolverError::NoSuchNumericTypeVariable { .. }));
+    }
+    #[test]
+    fn resolve_array_length_arithmetic() {
+        let src = r#"
+            fn main() {
+                let _a = [0; 2 * 4];
+                let _b = [0; 2 - 4];
+                let _c = [0; 4 / 0];
+                let _d = [0; 2 - 2];
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert_eq!(errors.len(), 2);
+        for err in errors {
+            assert!(matches!(err, ResolverError::InvalidArrayLengthArithmetic { .. }));
+        }
+    }
+
+    #[test]
+    fn resolve_parameter_defaults() {
+        let src = r#"
+            fn main(x : Field, y : [Field; 2] = [1, 2 * 3], z : u8 = -(4 as u8)) {
+                let _t = (x, y, z);
+            }
+        "#;
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert!(errors.is_empty());
+
+        let src = r#"
+            fn main(x : Field = 1, y : Field, z : Field = { 2 }) {
+                let _t = (x, y, z);
+            }
+        "#;
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ResolverError::UnsupportedParameterDefault { .. }));
+        assert!(matches!(errors[1], ResolverError::RequiredParameterAfterDefault { .. }));
+    }
 
     fn path_unresolved_error(err: ResolverError, expected_unresolved_path: &str) {
         match err {
@@ -3,7 +3,8 @@ use noirc_errors::Span;
 use thiserror::Error;
 
 use crate::hir_def::expr::HirBinaryOp;
-use crate::hir_def::types::Type;
+use crate::hir_def::types::{BinaryTypeOperator, Type};
+use crate::node_interner::ExprId;
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum TypeCheckError {
@@ -34,12 +35,95 @@ pub enum TypeCheckError {
     },
     #[error("Cannot infer type of expression, type annotations needed before this point")]
     TypeAnnotationsNeeded { span: Span },
+    #[error("Cannot pass a variable-length array where a fixed length is required")]
+    VariableArrayForFixedParameter { expected_typ: String, expr_typ: String, span: Span },
+    #[error("Chained comparisons are not supported")]
+    ChainedComparison { span: Span },
+    #[error("Loop would be unrolled {iterations} times, which exceeds the limit of {limit}")]
+    LoopIterationLimitExceeded { iterations: u128, limit: u64, span: Span },
+    #[error("Index {index} is out of bounds for an array of length {length}")]
+    IndexOutOfBounds { index: u128, length: u64, span: Span },
+    #[error("Division by zero")]
+    DivisionByZero { span: Span },
+    #[error("Constant {value} does not fit in the cast type {typ}")]
+    CastOverflow { value: u128, typ: Type, span: Span },
+    #[error("Constant {value} does not fit in type {typ}")]
+    ConstantOverflow { value: u128, typ: Type, span: Span },
+    #[error("No method named {method} found for type {typ}")]
+    UnknownMethod { typ: Type, method: String, span: Span },
+    #[error("Integer literal {}{value} is out of range for type {target}", sign(.negated))]
+    LiteralOutOfRange { value: u128, negated: bool, target: Type, span: Span },
+    #[error("Expected an array of length {expected} but found length {found}")]
+    ArrayLengthMismatch { expected: u64, found: u64, span: Span },
+    #[error("Struct {typ} has no field named {field}")]
+    UnknownField { typ: Type, field: String, span: Span },
+    #[error("Function {function} has no parameter named {name}")]
+    UnknownArgumentName { function: String, name: String, span: Span },
+    #[error("Argument {name} is given more than once")]
+    DuplicateArgumentName { name: String, span: Span },
+    #[error("Cannot use an integer and a Field in a binary operation, the Field should be cast to {integer_type}")]
+    WitnessIntegerMix { witness: ExprId, integer_type: Type, span: Span },
+    #[error("Array length {lhs} {op} {rhs} is not a valid length")]
+    InvalidArrayLength { lhs: u64, op: BinaryTypeOperator, rhs: u64, span: Span },
+    #[error("Named arguments can only be used when calling a function by name")]
+    NamedArgumentsOnNonFunction { span: Span },
+}
+
+/// Diagnostics which do not prevent compilation. These are kept apart from
+/// [`TypeCheckError`] so callers can decide whether to report or deny them.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TypeCheckWarning {
+    #[error("Large integer constant is implicitly a Field")]
+    LargeConstantPromotedToField { span: Span },
+    #[error("Casting a Field to {typ} may lose information")]
+    FieldToIntegerCast { typ: Type, span: Span },
+    #[error("Branch is never taken as the condition is always {condition}")]
+    DeadBranch { condition: bool, span: Span },
+    #[error("Redundant cast, value is already of type {typ}")]
+    RedundantCast { typ: Type, span: Span },
+    #[error("Mixing signed and unsigned integers, both are treated as {typ}")]
+    SignednessMix { typ: Type, span: Span },
 }
 
 impl TypeCheckError {
     pub fn add_context(self, ctx: &'static str) -> Self {
         TypeCheckError::Context { err: Box::new(self), ctx }
     }
+
+    /// A stable code identifying the kind of this error, e.g. `TC001`.
+    ///
+    /// Codes are never reassigned once published: new variants take the next
+    /// unused number and removed variants leave a gap.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeCheckError::ArityMisMatch { .. } => "TC001",
+            TypeCheckError::NonHomogeneousArray { .. } => "TC002",
+            TypeCheckError::TypeMismatch { .. } => "TC003",
+            TypeCheckError::OpCannotBeUsed { .. } => "TC004",
+            TypeCheckError::TypeCannotBeUsed { .. } => "TC005",
+            TypeCheckError::PublicReturnType { .. } => "TC006",
+            TypeCheckError::TypeAnnotationsNeeded { .. } => "TC007",
+            TypeCheckError::Unstructured { .. } => "TC008",
+            TypeCheckError::VariableArrayForFixedParameter { .. } => "TC010",
+            TypeCheckError::ChainedComparison { .. } => "TC012",
+            TypeCheckError::LoopIterationLimitExceeded { .. } => "TC013",
+            TypeCheckError::IndexOutOfBounds { .. } => "TC015",
+            TypeCheckError::DivisionByZero { .. } => "TC017",
+            TypeCheckError::CastOverflow { .. } => "TC018",
+            TypeCheckError::ConstantOverflow { .. } => "TC019",
+            TypeCheckError::ArrayLengthMismatch { .. } => "TC020",
+            TypeCheckError::UnknownField { .. } => "TC021",
+            TypeCheckError::UnknownArgumentName { .. } => "TC022",
+            TypeCheckError::DuplicateArgumentName { .. } => "TC023",
+            TypeCheckError::WitnessIntegerMix { .. } => "TC025",
+            TypeCheckError::LiteralOutOfRange { .. } => "TC026",
+            TypeCheckError::UnknownMethod { .. } => "TC027",
+            TypeCheckError::InvalidArrayLength { .. } => "TC028",
+            TypeCheckError::NamedArgumentsOnNonFunction { .. } => "TC029",
+            // Context only annotates another error, so it reports the code of that error
+            TypeCheckError::Context { err, .. } => err.code(),
+        }
+    }
 }
 
 impl From<TypeCheckError> for Diagnostic {
@@ -103,6 +187,223 @@ impl From<TypeCheckError> for Diagnostic {
                 "Type must be known at this point".to_string(),
                 span,
             ),
+            TypeCheckError::VariableArrayForFixedParameter { expected_typ, expr_typ, span } => {
+                Diagnostic::simple_error(
+                    "Cannot pass a variable-length array where a fixed length is required"
+                        .to_string(),
+                    format!("Expected type {expected_typ}, found type {expr_typ}"),
+                    span,
+                )
+            }
+            TypeCheckError::ChainedComparison { span } => Diagnostic::simple_error(
+                "Chained comparisons are not supported".to_string(),
+                "Use `(a < b) & (b < c)` to compare a value against both bounds".to_string(),
+                span,
+            ),
+            TypeCheckError::LoopIterationLimitExceeded { iterations, limit, span } => {
+                Diagnostic::simple_error(
+                    format!("This loop would be unrolled {iterations} times, which exceeds the limit of {limit} iterations"),
+                    "Loops are fully unrolled in a circuit, consider a smaller range".to_string(),
+                    span,
+                )
+            }
+            TypeCheckError::IndexOutOfBounds { index, length, span } => Diagnostic::simple_error(
+                format!("Index {index} is out of bounds for an array of length {length}"),
+                String::new(),
+                span,
+            ),
+            TypeCheckError::DivisionByZero { span } => Diagnostic::simple_error(
+                "Attempted to divide by zero".to_string(),
+                "The divisor is always zero".to_string(),
+                span,
+            ),
+            TypeCheckError::CastOverflow { value, typ, span } => Diagnostic::simple_error(
+                format!("The constant {value} does not fit in type {typ}"),
+                "Casting it would truncate its value".to_string(),
+                span,
+            ),
+            TypeCheckError::ConstantOverflow { value, typ, span } => Diagnostic::simple_error(
+                format!("The constant {value} does not fit in type {typ}"),
+                "This value is known at compile-time to overflow".to_string(),
+                span,
+            ),
+            TypeCheckError::LiteralOutOfRange { value, negated, target, span } => {
+                let sign = sign(&negated);
+                Diagnostic::simple_error(
+                    format!("The integer literal {sign}{value} is out of range for type {target}"),
+                    format!("{target} cannot hold this value"),
+                    span,
+                )
+            }
+            TypeCheckError::ArrayLengthMismatch { expected, found, span } => {
+                Diagnostic::simple_error(
+                    format!("Expected an array of length {expected} but got length {found}"),
+                    String::new(),
+                    span,
+                )
+            }
+            TypeCheckError::UnknownField { typ, field, span } => Diagnostic::simple_error(
+                format!("Struct {typ} has no field named {field}"),
+                String::new(),
+                span,
+            ),
+            TypeCheckError::UnknownMethod { typ, method, span } => Diagnostic::simple_error(
+                format!("No method named '{method}' found for type '{typ}'"),
+                String::new(),
+                span,
+            ),
+            TypeCheckError::UnknownArgumentName { function, name, span } => {
+                Diagnostic::simple_error(
+                    format!("Function {function} has no parameter named {name}"),
+                    String::new(),
+                    span,
+                )
+            }
+            TypeCheckError::DuplicateArgumentName { name, span } => Diagnostic::simple_error(
+                format!("Argument {name} is given more than once"),
+                "Each parameter may only be named once".into(),
+                span,
+            ),
+            TypeCheckError::WitnessIntegerMix { integer_type, span, .. } => {
+                Diagnostic::simple_error(
+                    "Cannot use an integer and a Field in a binary operation".into(),
+                    format!("Try converting this Field into an integer with `as {integer_type}`"),
+                    span,
+                )
+            }
+            TypeCheckError::InvalidArrayLength { lhs, op, rhs, span } => {
+                Diagnostic::simple_error(
+                    format!("The array length {lhs} {op} {rhs} is not a valid length"),
+                    "It overflows, goes below zero, or divides by zero".to_string(),
+                    span,
+                )
+            }
+            TypeCheckError::NamedArgumentsOnNonFunction { span } => Diagnostic::simple_error(
+                "Named arguments can only be used when calling a function by name".to_string(),
+                "This is not the name of a function".to_string(),
+                span,
+            ),
+        }
+    }
+}
+
+impl TypeCheckWarning {
+    /// A stable code identifying the kind of this warning. Warnings share the
+    /// numbering of [`TypeCheckError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeCheckWarning::LargeConstantPromotedToField { .. } => "TC009",
+            TypeCheckWarning::FieldToIntegerCast { .. } => "TC011",
+            TypeCheckWarning::DeadBranch { .. } => "TC014",
+            TypeCheckWarning::RedundantCast { .. } => "TC016",
+            TypeCheckWarning::SignednessMix { .. } => "TC024",
+        }
+    }
+}
+
+impl From<TypeCheckWarning> for Diagnostic {
+    fn from(warning: TypeCheckWarning) -> Diagnostic {
+        match warning {
+            TypeCheckWarning::LargeConstantPromotedToField { span } => Diagnostic::simple_warning(
+                "Large integer constant is implicitly given the Field type".to_string(),
+                "Add a type annotation if this constant should be a Field".to_string(),
+                span,
+            ),
+            TypeCheckWarning::FieldToIntegerCast { typ, span } => Diagnostic::simple_warning(
+                format!("Casting a Field to {typ} may lose information"),
+                format!("Field values which do not fit in {typ} will be truncated. Consider range checking the value first"),
+                span,
+            ),
+            TypeCheckWarning::DeadBranch { condition, span } => Diagnostic::simple_warning(
+                "This branch is never taken".to_string(),
+                format!("The condition of this if is always {condition}"),
+                span,
+            ),
+            TypeCheckWarning::RedundantCast { typ, span } => Diagnostic::simple_warning(
+                format!("Redundant cast, value is already of type {typ}"),
+                "This cast can be removed".to_string(),
+                span,
+            ),
+            TypeCheckWarning::SignednessMix { typ, span } => Diagnostic::simple_warning(
+                format!("Mixing signed and unsigned integers, both are treated as {typ}"),
+                "Cast the unsigned operand to make the conversion explicit".to_string(),
+                span,
+            ),
         }
     }
 }
+
+/// The sign to print before a literal which may have been negated
+fn sign(negated: &bool) -> &'static str {
+    if *negated {
+        "-"
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use noirc_errors::Span;
+
+    use super::{TypeCheckError, TypeCheckWarning};
+
+    #[test]
+    fn error_codes_are_stable() {
+        let span = Span::default();
+        let arity = TypeCheckError::ArityMisMatch { expected: 1, found: 2, span };
+        assert_eq!(arity.code(), "TC001");
+
+        let array = TypeCheckError::NonHomogeneousArray {
+            first_span: span,
+            first_type: "Field".into(),
+            first_index: 0,
+            second_span: span,
+            second_type: "bool".into(),
+            second_index: 1,
+        };
+        assert_eq!(array.code(), "TC002");
+
+        let mismatch = TypeCheckError::TypeMismatch {
+            expected_typ: "Field".into(),
+            expr_typ: "bool".into(),
+            expr_span: span,
+        };
+        assert_eq!(mismatch.add_context("in a let statement").code(), "TC003");
+
+        assert_eq!(TypeCheckError::DivisionByZero { span }.code(), "TC017");
+
+        let duplicate = TypeCheckError::DuplicateArgumentName { name: "x".into(), span };
+        assert_eq!(duplicate.code(), "TC023");
+
+        let witness = crate::node_interner::ExprId::empty_block_id();
+        let mix =
+            TypeCheckError::WitnessIntegerMix { witness, integer_type: crate::Type::Unit, span };
+        assert_eq!(mix.code(), "TC025");
+
+        let out_of_range = TypeCheckError::LiteralOutOfRange {
+            value: 256,
+            negated: false,
+            target: crate::Type::Unit,
+            span,
+        };
+        assert_eq!(out_of_range.code(), "TC026");
+
+        let method = String::from("foo");
+        let unknown_method = TypeCheckError::UnknownMethod { typ: crate::Type::Unit, method, span };
+        assert_eq!(unknown_method.code(), "TC027");
+
+        let op = crate::BinaryTypeOperator::Subtraction;
+        let invalid_length = TypeCheckError::InvalidArrayLength { lhs: 0, op, rhs: 1, span };
+        assert_eq!(invalid_length.code(), "TC028");
+
+        let named = TypeCheckError::NamedArgumentsOnNonFunction { span };
+        assert_eq!(named.code(), "TC029");
+
+        let large_constant = TypeCheckWarning::LargeConstantPromotedToField { span };
+        assert_eq!(large_constant.code(), "TC009");
+
+        let mix = TypeCheckWarning::SignednessMix { typ: crate::Type::Unit, span };
+        assert_eq!(mix.code(), "TC024");
+    }
+}
@@ -1,16 +1,25 @@
+use acvm::FieldElement;
 use iter_extended::vecmap;
-use noirc_errors::Span;
+use noirc_errors::{Span, Spanned};
 
 use crate::{
     hir_def::{
-        expr::{self, HirArrayLiteral, HirBinaryOp, HirExpression, HirLiteral},
+        expr::{
+            self, HirArrayLiteral, HirBinaryOp, HirCallExpression, HirCastExpression,
+            HirExpression, HirLiteral, HirMethodCallExpression,
+        },
+        function::{get_param_name, FuncMeta},
         types::Type,
     },
-    node_interner::{ExprId, FuncId},
-    CompTime, Shared, TypeBinding,
+    node_interner::{DefinitionKind, ExprId, FuncId},
+    token::Attribute,
+    BinaryOpKind, CompTime, Ident, Shared, Signedness, TypeBinding,
 };
 
-use super::{errors::TypeCheckError, TypeChecker};
+use super::{
+    errors::{TypeCheckError, TypeCheckWarning},
+    IntegerLiteral, TypeChecker,
+};
 
 impl<'interner> TypeChecker<'interner> {
     /// Infers a type for a given expression, and return this type.
@@ -38,14 +47,19 @@ impl<'interner> TypeChecker<'interner> {
                     HirLiteral::Array(HirArrayLiteral::Standard(arr)) => {
                         let elem_types = vecmap(&arr, |arg| self.check_expression(arg));
 
-                        let first_elem_type = elem_types.get(0).cloned().unwrap_or(Type::Error);
+                        // The elements of an empty array may be of any type
+                        let first_elem_type = elem_types
+                            .get(0)
+                            .cloned()
+                            .unwrap_or_else(|| self.interner.next_type_variable());
 
                         let arr_type = Type::Array(
                             Box::new(Type::Constant(arr.len() as u64)),
                             Box::new(first_elem_type.clone()),
                         );
 
-                        // Check if the array is homogeneous
+                        // Check if the array is homogeneous. Every element is compared against the
+                        // first so that each differing element is reported with its own index.
                         for (index, elem_type) in elem_types.iter().enumerate().skip(1) {
                             let location = self.interner.expr_location(&arr[index]);
 
@@ -57,10 +71,10 @@ impl<'interner> TypeChecker<'interner> {
                                     TypeCheckError::NonHomogeneousArray {
                                         first_span: self.interner.expr_location(&arr[0]).span,
                                         first_type: first_elem_type.to_string(),
-                                        first_index: index,
+                                        first_index: 0,
                                         second_span: location.span,
                                         second_type: elem_type.to_string(),
-                                        second_index: index + 1,
+                                        second_index: index,
                                     }
                                     .add_context("elements in an array must have the same type")
                                 },
@@ -74,13 +88,24 @@ impl<'interner> TypeChecker<'interner> {
                         Type::Array(Box::new(length), Box::new(elem_type))
                     }
                     HirLiteral::Bool(_) => Type::Bool(CompTime::new(self.interner)),
-                    HirLiteral::Integer(_) => {
+                    HirLiteral::Integer(value) => {
                         let id = self.interner.next_type_variable_id();
-                        Type::PolymorphicInteger(
+                        let typ = Type::PolymorphicInteger(
                             CompTime::new(self.interner),
                             Shared::new(TypeBinding::Unbound(id)),
-                        )
+                        );
+                        self.check_large_constant(expr_id, value, typ.clone());
+                        if value.num_bits() <= 128 {
+                            let value = value.to_u128();
+                            let span = self.interner.expr_span(expr_id);
+                            let literal =
+                                IntegerLiteral { value, negated: false, typ: typ.clone(), span };
+                            self.integer_literals.push(literal);
+                        }
+                        typ
                     }
+                    // Unlike other integer literals these can never become a sized integer
+                    HirLiteral::Field(_) => Type::FieldElement(CompTime::new(self.interner)),
                     HirLiteral::Str(string) => {
                         let len = Type::Constant(string.len() as u64);
                         Type::String(Box::new(len))
@@ -96,34 +121,79 @@ impl<'interner> TypeChecker<'interner> {
                 let rhs_span = self.interner.expr_span(&infix_expr.rhs);
                 let span = lhs_span.merge(rhs_span);
 
-                self.infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
-                    .unwrap_or_else(|error| {
-                        self.errors.push(error);
-                        Type::Error
-                    })
+                let operator_method = infix_expr.operator.kind.method_name();
+                if let (Some(method_name), Type::Struct(..)) =
+                    (operator_method, lhs_type.follow_bindings())
+                {
+                    let args = vec![
+                        (lhs_type, infix_expr.lhs, lhs_span),
+                        (rhs_type, infix_expr.rhs, rhs_span),
+                    ];
+                    self.check_operator_method(expr_id, infix_expr, method_name, args, span)
+                } else if infix_expr.operator.kind.is_ordering()
+                    && self.is_comparison(&infix_expr.lhs)
+                {
+                    // `a < b < c` parses as `(a < b) < c`, which compares a bool against `c`.
+                    // Report the likely intent rather than the resulting type mismatch.
+                    self.errors.push(TypeCheckError::ChainedComparison { span });
+                    Type::Bool(CompTime::No(Some(span)))
+                } else {
+                    // Whether these need a range check depends on the final type of the
+                    // expression, which may not be known yet if both operands are polymorphic.
+                    if infix_expr.operator.kind.may_overflow() {
+                        self.interner.push_range_check(*expr_id);
+                    }
+
+                    self.check_division_by_zero(infix_expr.operator.kind, &infix_expr.rhs);
+
+                    let typ = self
+                        .infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
+                        .unwrap_or_else(|error| {
+                            let error = self
+                                .witness_integer_mix(&infix_expr, &lhs_type, &rhs_type)
+                                .unwrap_or(error);
+                            self.errors.push(error);
+                            Type::Error
+                        });
+
+                    if infix_expr.operator.kind == BinaryOpKind::Modulo {
+                        self.check_field_modulo(&typ, span);
+                    }
+
+                    // The u1 operand of an operation with a bool is converted to a bool
+                    if typ != Type::Error && is_u1_and_bool(&lhs_type, &rhs_type) {
+                        self.cast_u1_to_bool(&infix_expr.lhs, &lhs_type);
+                        self.cast_u1_to_bool(&infix_expr.rhs, &rhs_type);
+                    }
+                    typ
+                }
             }
             HirExpression::Index(index_expr) => self.check_index_expression(index_expr),
+            HirExpression::Slice(slice_expr) => self.check_slice_expression(slice_expr, expr_id),
             HirExpression::Call(call_expr) => {
+                let (call_expr, names_matched) = self.order_call_arguments(expr_id, call_expr);
                 let function = self.check_expression(&call_expr.func);
                 let args = vecmap(&call_expr.arguments, |arg| {
                     let typ = self.check_expression(arg);
-                    (typ, self.interner.expr_span(arg))
+                    (typ, *arg, self.interner.expr_span(arg))
                 });
-                let span = self.interner.expr_span(expr_id);
-                self.bind_function_type(function, args, span)
+
+                if names_matched {
+                    let span = self.interner.expr_span(expr_id);
+                    let ret = self.bind_function_type(function, args, span);
+                    self.check_bit_decomposition(&call_expr.func, &call_expr.arguments, ret)
+                } else {
+                    // The arguments cannot be matched to the parameters, which is already reported
+                    Type::Error
+                }
             }
             HirExpression::MethodCall(method_call) => {
                 let object_type = self.check_expression(&method_call.object);
                 let method_name = method_call.method.0.contents.as_str();
                 match self.lookup_method(object_type.clone(), method_name, expr_id) {
                     Some(method_id) => {
-                        let mut args =
-                            vec![(object_type, self.interner.expr_span(&method_call.object))];
-                        let mut arg_types = vecmap(&method_call.arguments, |arg| {
-                            let typ = self.check_expression(arg);
-                            (typ, self.interner.expr_span(arg))
-                        });
-                        args.append(&mut arg_types);
+                        let object = method_call.object;
+                        let object_span = self.interner.expr_span(&object);
 
                         // Desugar the method call into a normal, resolved function call
                         // so that the backend doesn't need to worry about methods
@@ -131,10 +201,26 @@ impl<'interner> TypeChecker<'interner> {
                         let (function_id, function_call) =
                             method_call.into_function_call(method_id, location, self.interner);
 
+                        // Default values can only be given once the method is known
+                        let (function_call, _) = self.order_call_arguments(expr_id, function_call);
+
+                        // The object was already checked to find the method
+                        let mut args = vec![(object_type, object, object_span)];
+                        let mut arg_types = vecmap(&function_call.arguments[1..], |arg| {
+                            let typ = self.check_expression(arg);
+                            (typ, *arg, self.interner.expr_span(arg))
+                        });
+                        args.append(&mut arg_types);
+
                         let span = self.interner.expr_span(expr_id);
                         let ret = self.check_method_call(&function_id, &method_id, args, span);
+                        let ret = self.check_bit_decomposition(
+                            &function_id,
+                            &function_call.arguments,
+                            ret,
+                        );
 
-                        self.interner.replace_expr(expr_id, function_call);
+                        self.interner.replace_expr(expr_id, HirExpression::Call(function_call));
                         ret
                     }
                     None => Type::Error,
@@ -144,6 +230,8 @@ impl<'interner> TypeChecker<'interner> {
                 // Evaluate the LHS
                 let lhs_type = self.check_expression(&cast_expr.lhs);
                 let span = self.interner.expr_span(expr_id);
+                self.check_redundant_cast(&lhs_type, &cast_expr.r#type, span);
+                self.check_cast_overflow(&cast_expr.lhs, &cast_expr.r#type, span);
                 self.check_cast(lhs_type, cast_expr.r#type, span)
             }
             HirExpression::For(for_expr) => {
@@ -153,8 +241,11 @@ impl<'interner> TypeChecker<'interner> {
                 let start_span = self.interner.expr_span(&for_expr.start_range);
                 let end_span = self.interner.expr_span(&for_expr.end_range);
 
+                let allow_dynamic_loops = self.is_unconstrained()
+                    || self.interner.type_check_options().allow_dynamic_loops;
+
                 let mut unify_loop_range = |actual_type, span| {
-                    let expected_type = if self.is_unconstrained() {
+                    let expected_type = if allow_dynamic_loops {
                         Type::FieldElement(CompTime::new(self.interner))
                     } else {
                         Type::comp_time(Some(span))
@@ -172,12 +263,37 @@ impl<'interner> TypeChecker<'interner> {
 
                 unify_loop_range(&start_range_type, start_span);
                 unify_loop_range(&end_range_type, end_span);
+                self.check_loop_iterations(&for_expr, start_span.merge(end_span));
 
                 self.interner.push_definition_type(for_expr.identifier.id, start_range_type);
 
                 self.check_expression(&for_expr.block);
                 Type::Unit
             }
+            HirExpression::While(while_expr) => {
+                let cond_type = self.check_expression(&while_expr.condition);
+                let cond_span = self.interner.expr_span(&while_expr.condition);
+
+                let bool_type = Type::Bool(CompTime::new(self.interner));
+                self.unify(&cond_type, &bool_type, cond_span, || TypeCheckError::TypeMismatch {
+                    expected_typ: Type::Bool(CompTime::No(None)).to_string(),
+                    expr_typ: cond_type.to_string(),
+                    expr_span: cond_span,
+                });
+
+                // The number of iterations of a while loop is only known at runtime. Unlike for
+                // loops with runtime bounds, allow_dynamic_loops does not apply since there is
+                // no way to lower a while loop in a constrained function yet.
+                if !self.is_unconstrained() {
+                    self.errors.push(TypeCheckError::Unstructured {
+                        msg: "While loops are only allowed in unconstrained functions".into(),
+                        span: self.interner.expr_span(expr_id),
+                    });
+                }
+
+                self.check_expression(&while_expr.block);
+                Type::Unit
+            }
             HirExpression::Block(block_expr) => {
                 let mut block_type = Type::Unit;
 
@@ -199,6 +315,13 @@ impl<'interner> TypeChecker<'interner> {
                                 expr_span: span,
                             }
                         });
+                    } else if matches!(
+                        self.interner.statement(stmt),
+                        crate::hir_def::stmt::HirStatement::Return(_)
+                    ) {
+                        // A block ending in a return never produces a value, so it is given
+                        // a fresh type to fit wherever it is used, such as a branch of an if
+                        block_type = self.interner.next_type_variable();
                     } else {
                         block_type = expr_type;
                     }
@@ -208,11 +331,19 @@ impl<'interner> TypeChecker<'interner> {
             }
             HirExpression::Prefix(prefix_expr) => {
                 let rhs_type = self.check_expression(&prefix_expr.rhs);
-                match prefix_operand_type_rules(&prefix_expr.operator, &rhs_type) {
+                let rhs_span = self.interner.expr_span(&prefix_expr.rhs);
+
+                // The literal was the last one pushed, as it was the rhs just checked
+                let is_literal = self.interner.integer_literal(prefix_expr.rhs).is_some();
+                if prefix_expr.operator == crate::UnaryOp::Minus && is_literal {
+                    if let Some(literal) = self.integer_literals.last_mut() {
+                        literal.negated = true;
+                    }
+                }
+                match prefix_operand_type_rules(&prefix_expr.operator, &rhs_type, rhs_span) {
                     Ok(typ) => typ,
-                    Err(msg) => {
-                        let rhs_span = self.interner.expr_span(&prefix_expr.rhs);
-                        self.errors.push(TypeCheckError::Unstructured { msg, span: rhs_span });
+                    Err(error) => {
+                        self.errors.push(error);
                         Type::Error
                     }
                 }
@@ -230,7 +361,9 @@ impl<'interner> TypeChecker<'interner> {
                     typ
                 });
 
+                let in_lambda = std::mem::replace(&mut self.in_lambda, true);
                 let actual_return = self.check_expression(&lambda.body);
+                self.in_lambda = in_lambda;
 
                 let span = self.interner.expr_span(&lambda.body);
                 actual_return.make_subtype_of(&lambda.return_type, span, &mut self.errors, || {
@@ -262,9 +395,14 @@ impl<'interner> TypeChecker<'interner> {
 
         let lhs_type = self.check_expression(&index_expr.collection);
         match lhs_type {
-            // XXX: We can check the array bounds here also, but it may be better to constant fold first
-            // and have ConstId instead of ExprId for constants
-            Type::Array(_, base_type) => *base_type,
+            Type::Array(length, base_type) => {
+                self.check_index_in_bounds(&index_expr.index, &length);
+                *base_type
+            }
+            Type::String(length) => {
+                self.check_index_in_bounds(&index_expr.index, &length);
+                Type::Char
+            }
             Type::Error => Type::Error,
             typ => {
                 let span = self.interner.expr_span(&index_expr.collection);
@@ -278,15 +416,349 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// A slice with literal bounds is an array of `end - start` elements. Otherwise its length
+    /// is only known at runtime, so the slice is a Vec, which may only be used in unconstrained
+    /// functions.
+    fn check_slice_expression(
+        &mut self,
+        slice_expr: expr::HirSliceExpression,
+        expr_id: &ExprId,
+    ) -> Type {
+        self.check_slice_bound(&slice_expr.start);
+        self.check_slice_bound(&slice_expr.end);
+
+        let collection_type = self.check_expression(&slice_expr.collection);
+        let (length, element) = match collection_type.follow_bindings() {
+            Type::Array(length, element) => (length, element),
+            Type::Error => return Type::Error,
+            typ => {
+                let span = self.interner.expr_span(&slice_expr.collection);
+                self.errors.push(TypeCheckError::TypeMismatch {
+                    expected_typ: "Array".to_owned(),
+                    expr_typ: typ.to_string(),
+                    expr_span: span,
+                });
+                return Type::Error;
+            }
+        };
+
+        let start = self.interner.integer_literal(slice_expr.start);
+        let end = self.interner.integer_literal(slice_expr.end);
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                let span = self.interner.expr_span(expr_id);
+                if start > end {
+                    self.errors.push(TypeCheckError::Unstructured {
+                        msg: format!(
+                            "The start of a slice must not be after its end, found {start}..{end}"
+                        ),
+                        span,
+                    });
+                    return Type::Error;
+                }
+                if let Some(length) = length.evaluate_to_u64() {
+                    if end > length as u128 {
+                        let span = self.interner.expr_span(&slice_expr.end);
+                        self.errors.push(TypeCheckError::IndexOutOfBounds {
+                            index: end,
+                            length,
+                            span,
+                        });
+                    }
+                }
+                Type::Array(Box::new(Type::Constant((end - start) as u64)), element)
+            }
+            // The length of the slice would only be known at runtime, which no array type can
+            // express, and lowering such a slice to a Vec is not yet implemented.
+            _ => {
+                self.errors.push(TypeCheckError::Unstructured {
+                    msg: "The bounds of a slice must be integer literals. Slicing with runtime bounds is currently unsupported".into(),
+                    span: self.interner.expr_span(expr_id),
+                });
+                Type::Error
+            }
+        }
+    }
+
+    /// The bounds of a slice index into an array, so like an index they may be a Field,
+    /// or any unsigned integer
+    fn check_slice_bound(&mut self, bound: &ExprId) {
+        let bound_type = self.check_expression(bound);
+        if matches!(bound_type.follow_bindings(), Type::Integer(_, Signedness::Unsigned, _)) {
+            return;
+        }
+
+        let span = self.interner.expr_span(bound);
+        bound_type.make_subtype_of(&Type::field(Some(span)), span, &mut self.errors, || {
+            TypeCheckError::TypeMismatch {
+                expected_typ: "Field or an unsigned integer".to_owned(),
+                expr_typ: bound_type.to_string(),
+                expr_span: span,
+            }
+        });
+    }
+
+    /// The builtin `to_le_bits` and `to_be_bits` return as many bits as requested by their
+    /// second argument, which must be known at compile-time. If it is an integer literal,
+    /// the returned array is given exactly that length.
+    fn check_bit_decomposition(
+        &mut self,
+        function: &ExprId,
+        arguments: &[ExprId],
+        return_type: Type,
+    ) -> Type {
+        match self.builtin_name(function).as_deref() {
+            Some("to_le_bits" | "to_be_bits") if arguments.len() == 2 => (),
+            _ => return return_type,
+        }
+
+        let bit_size = &arguments[1];
+        let span = self.interner.expr_span(bit_size);
+        let bit_size_type = self.interner.id_type(bit_size);
+        if !self.is_unconstrained() && is_runtime_value(&bit_size_type) {
+            self.errors.push(TypeCheckError::Unstructured {
+                msg: format!(
+                    "The number of bits must be known at compile-time, found {bit_size_type}"
+                ),
+                span,
+            });
+        }
+
+        if let (Some(bits), Type::Array(length, _)) =
+            (self.interner.integer_literal(*bit_size), return_type.follow_bindings())
+        {
+            let expected = Type::Constant(bits as u64);
+            self.unify(&length, &expected, span, || TypeCheckError::TypeMismatch {
+                expected_typ: expected.to_string(),
+                expr_typ: length.to_string(),
+                expr_span: span,
+            });
+        }
+        return_type
+    }
+
+    /// The name given in the `#[builtin(..)]` attribute of the function the given expression
+    /// refers to, if any
+    fn builtin_name(&self, function: &ExprId) -> Option<String> {
+        let ident = match self.interner.expression(function) {
+            HirExpression::Ident(ident) => ident,
+            _ => return None,
+        };
+        let func_id = match self.interner.definition(ident.id).kind {
+            DefinitionKind::Function(func_id) => func_id,
+            _ => return None,
+        };
+        match self.interner.try_function_meta(&func_id)?.attributes {
+            Some(Attribute::Builtin(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Error if the given index is an integer literal past the end of an array of the given length.
+    /// Indices computed at runtime are left to be checked when the circuit is evaluated.
+    pub(crate) fn check_index_in_bounds(&mut self, index: &ExprId, length: &Type) {
+        let index_value = self.interner.integer_literal(*index);
+        if let (Some(index_value), Some(length)) = (index_value, length.evaluate_to_u64()) {
+            if index_value >= length as u128 {
+                let span = self.interner.expr_span(index);
+                self.errors.push(TypeCheckError::IndexOutOfBounds {
+                    index: index_value,
+                    length,
+                    span,
+                });
+            }
+        }
+    }
+
+    /// Error if a loop whose range has literal bounds exceeds the configured iteration limit.
+    /// Each iteration is unrolled into the circuit, so a large range can make it enormous.
+    fn check_loop_iterations(&mut self, for_expr: &expr::HirForExpression, span: Span) {
+        let limit = match self.interner.type_check_options().max_loop_iterations {
+            Some(limit) => limit,
+            None => return,
+        };
+
+        let start = self.interner.integer_literal(for_expr.start_range);
+        let end = self.interner.integer_literal(for_expr.end_range);
+        if let (Some(start), Some(end)) = (start, end) {
+            let iterations = end.saturating_sub(start);
+            if iterations > limit as u128 {
+                let error = TypeCheckError::LoopIterationLimitExceeded { iterations, limit, span };
+                self.errors.push(error);
+            }
+        }
+    }
+
+    /// Error if a constant is cast to an integer type too small to hold it
+    fn check_cast_overflow(&mut self, lhs: &ExprId, to: &Type, span: Span) {
+        if let Some(value) = self.interner.constant_value(*lhs) {
+            if !fits_in_type(value, to) {
+                self.errors.push(TypeCheckError::CastOverflow { value, typ: to.clone(), span });
+            }
+        }
+    }
+
+    /// Error if a constant expression is given an integer type too small to hold it,
+    /// e.g. `let x: u8 = 200 + 100;`. Plain literals are left to [`Self::check_literal_ranges`].
+    pub(crate) fn check_constant_fits(&mut self, expr_id: &ExprId, typ: &Type) {
+        if self.interner.integer_literal(*expr_id).is_some() {
+            return;
+        }
+        if let Some(value) = self.interner.constant_value(*expr_id) {
+            if !fits_in_type(value, typ) {
+                let span = self.interner.expr_span(expr_id);
+                self.errors.push(TypeCheckError::ConstantOverflow {
+                    value,
+                    typ: typ.clone(),
+                    span,
+                });
+            }
+        }
+    }
+
+    /// Error for each integer literal whose value is outside the range of the integer type
+    /// it was given, e.g. `let x: u8 = 300;` or `-129` as an i8
+    pub(crate) fn check_literal_ranges(&mut self) {
+        for literal in std::mem::take(&mut self.integer_literals) {
+            let target = literal.typ.follow_bindings();
+            let fits = match target {
+                // The magnitude of a negative value may be one more than the largest positive one
+                Type::Integer(_, Signedness::Signed, _) if literal.negated => {
+                    fits_in_type(literal.value.saturating_sub(1), &target)
+                }
+                // Only zero may be negated when the target cannot hold negative values
+                Type::Integer(_, Signedness::Unsigned, _) if literal.negated => literal.value == 0,
+                _ => fits_in_type(literal.value, &target),
+            };
+
+            if !fits {
+                let (value, negated, span) = (literal.value, literal.negated, literal.span);
+                let error = TypeCheckError::LiteralOutOfRange { value, negated, target, span };
+                self.errors.push(error);
+            }
+        }
+    }
+
+    /// Error if the divisor of a division or modulo operation is the literal `0`
+    fn check_division_by_zero(&mut self, operator: BinaryOpKind, divisor: &ExprId) {
+        let is_division = matches!(operator, BinaryOpKind::Divide | BinaryOpKind::Modulo);
+        if is_division && self.interner.integer_literal(*divisor) == Some(0) {
+            let span = self.interner.expr_span(divisor);
+            self.errors.push(TypeCheckError::DivisionByZero { span });
+        }
+    }
+
+    /// Wraps an already checked expression in a cast to the given type. The expression is
+    /// moved to a new id so that anything referring to the old id now sees the cast.
+    fn insert_cast(&mut self, expr_id: &ExprId, typ: Type) {
+        let expression = self.interner.expression(expr_id);
+        let expression_type = self.interner.id_type(expr_id);
+        let location = self.interner.expr_location(expr_id);
+
+        let lhs = self.interner.push_expr(expression);
+        self.interner.push_expr_type(&lhs, expression_type);
+        self.interner.push_expr_location(lhs, location.span, location.file);
+
+        let cast = HirCastExpression { lhs, r#type: typ.clone() };
+        self.interner.replace_expr(expr_id, HirExpression::Cast(cast));
+        self.interner.push_expr_type(expr_id, typ);
+    }
+
+    fn cast_u1_to_bool(&mut self, expr_id: &ExprId, typ: &Type) {
+        if let Type::Integer(comptime, Signedness::Unsigned, 1) = typ.follow_bindings() {
+            self.insert_cast(expr_id, Type::Bool(comptime));
+        }
+    }
+
+    /// Modulo is only defined on integers. If the operands are still polymorphic they may
+    /// default to a Field, so the check is delayed until the function finishes resolving.
+    fn check_field_modulo(&mut self, typ: &Type, span: Span) {
+        let make_error = move || {
+            TypeCheckError::Unstructured {
+            msg: "The modulo operator cannot be used on Field elements. Try casting the operands to a sized integer type first".into(),
+            span,
+        }
+        };
+
+        if typ.is_field() {
+            self.errors.push(make_error());
+        } else if typ.is_bindable() {
+            let typ = typ.clone();
+            self.interner.push_delayed_type_check(Box::new(move || {
+                if typ.is_field() || typ.is_bindable() {
+                    Err(make_error())
+                } else {
+                    Ok(())
+                }
+            }));
+        }
+    }
+
+    /// True if the given expression is itself an operation comparing two values
+    fn is_comparison(&self, expr_id: &ExprId) -> bool {
+        match self.interner.expression(expr_id) {
+            HirExpression::Infix(infix) => infix.operator.kind.is_comparator(),
+            _ => false,
+        }
+    }
+
+    /// Warn if an integer literal above the configured threshold is left to default to a Field.
+    /// Whether the literal defaults is only known once the whole function is checked, so the
+    /// check is delayed until then.
+    fn check_large_constant(&mut self, expr_id: &ExprId, value: FieldElement, typ: Type) {
+        let threshold = match self.interner.type_check_options().large_constant_threshold {
+            Some(threshold) => threshold,
+            None => return,
+        };
+
+        if value.num_bits() <= 128 && value.to_u128() <= threshold {
+            return;
+        }
+
+        let span = self.interner.expr_span(expr_id);
+        self.large_constants.push((span, typ));
+    }
+
+    /// Warn when a value is cast to the type it already has
+    fn check_redundant_cast(&mut self, from: &Type, to: &Type, span: Span) {
+        if !self.interner.type_check_options().warn_redundant_casts {
+            return;
+        }
+
+        let redundant = match (from.follow_bindings(), to) {
+            (Type::FieldElement(_), Type::FieldElement(_)) | (Type::Bool(_), Type::Bool(_)) => true,
+            (Type::Integer(_, from_sign, from_bits), Type::Integer(_, to_sign, to_bits)) => {
+                from_sign == *to_sign && from_bits == *to_bits
+            }
+            _ => false,
+        };
+
+        if redundant {
+            self.warnings.push(TypeCheckWarning::RedundantCast { typ: to.clone(), span });
+        }
+    }
+
     fn check_cast(&mut self, from: Type, to: Type, span: Span) -> Type {
+        let from_field = from.is_field();
         let is_comp_time = match from {
             Type::Integer(is_comp_time, ..) => is_comp_time,
-            Type::FieldElement(is_comp_time) => is_comp_time,
+            Type::FieldElement(is_comp_time) => {
+                if self.interner.type_check_options().warn_field_to_integer_casts {
+                    if let Type::Integer(..) = to {
+                        let typ = to.clone();
+                        self.warnings.push(TypeCheckWarning::FieldToIntegerCast { typ, span });
+                    }
+                }
+                is_comp_time
+            }
             Type::PolymorphicInteger(is_comp_time, binding) => match &*binding.borrow() {
                 TypeBinding::Bound(from) => return self.check_cast(from.clone(), to, span),
                 TypeBinding::Unbound(_) => is_comp_time,
             },
             Type::Bool(is_comp_time) => is_comp_time,
+            Type::Array(length, element) => {
+                return self.check_array_cast(*length, *element, to, span)
+            }
             Type::Error => return Type::Error,
             from => {
                 let msg = format!(
@@ -320,6 +792,13 @@ impl<'interner> TypeChecker<'interner> {
 
                 Type::FieldElement(is_comp_time)
             }
+            // A Field has no canonical truncation to a single bit
+            Type::Bool(_) if from_field => {
+                let msg =
+                    "Cannot cast a Field to bool, try comparing it against zero instead".into();
+                self.errors.push(TypeCheckError::Unstructured { msg, span });
+                Type::Error
+            }
             Type::Bool(dest_comp_time) => {
                 if dest_comp_time.is_comp_time()
                     && is_comp_time.unify(&dest_comp_time, span).is_err()
@@ -338,13 +817,43 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Arrays may be cast to arrays of the same length, provided each element may be cast
+    /// to the new element type. E.g. `[u8; 4]` may be cast to `[u16; 4]` but not to `[u16; 2]`.
+    fn check_array_cast(
+        &mut self,
+        from_length: Type,
+        from_element: Type,
+        to: Type,
+        span: Span,
+    ) -> Type {
+        match to {
+            Type::Array(to_length, to_element) => {
+                from_length.unify(&to_length, span, &mut self.errors, || {
+                    let msg = format!(
+                        "Cannot cast an array of length {from_length} to an array of length {to_length}"
+                    );
+                    TypeCheckError::Unstructured { msg, span }
+                });
+
+                let element = self.check_cast(from_element, *to_element, span);
+                Type::Array(to_length, Box::new(element))
+            }
+            Type::Error => Type::Error,
+            to => {
+                let msg = format!("Arrays may only be cast to other arrays, found {to}");
+                self.errors.push(TypeCheckError::Unstructured { msg, span });
+                Type::Error
+            }
+        }
+    }
+
     // We need a special function to type check method calls since the method
     // is not a Expression::Ident it must be manually instantiated here
     fn check_method_call(
         &mut self,
         function_ident_id: &ExprId,
         func_id: &FuncId,
-        arguments: Vec<(Type, Span)>,
+        arguments: Vec<(Type, ExprId, Span)>,
         span: Span,
     ) -> Type {
         if func_id == &FuncId::dummy_id() {
@@ -373,6 +882,37 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Operators applied to a struct are implemented by a method on that struct.
+    /// Desugar `a + b` into the call `Foo::add(a, b)` and check it like any other call.
+    fn check_operator_method(
+        &mut self,
+        expr_id: &ExprId,
+        infix_expr: expr::HirInfixExpression,
+        method_name: &str,
+        args: Vec<(Type, ExprId, Span)>,
+        span: Span,
+    ) -> Type {
+        let struct_type = args[0].0.follow_bindings();
+        let method_id = match self.lookup_method(struct_type, method_name, expr_id) {
+            Some(method_id) => method_id,
+            None => return Type::Error,
+        };
+
+        let location = infix_expr.operator.location;
+        let method_call = HirMethodCallExpression {
+            method: Ident(Spanned::from(location.span, method_name.to_owned())),
+            object: infix_expr.lhs,
+            arguments: vec![infix_expr.rhs],
+            location,
+        };
+        let (function_id, function_call) =
+            method_call.into_function_call(method_id, location, self.interner);
+
+        let ret = self.check_method_call(&function_id, &method_id, args, span);
+        self.interner.replace_expr(expr_id, HirExpression::Call(function_call));
+        ret
+    }
+
     fn check_if_expr(&mut self, if_expr: &expr::HirIfExpression, expr_id: &ExprId) -> Type {
         let cond_type = self.check_expression(&if_expr.condition);
         let then_type = self.check_expression(&if_expr.consequence);
@@ -386,6 +926,11 @@ impl<'interner> TypeChecker<'interner> {
             expr_span,
         });
 
+        let constant_condition = self.bool_literal(&if_expr.condition);
+        if let Some(condition) = constant_condition {
+            self.check_dead_branch(if_expr, condition);
+        }
+
         match if_expr.alternative {
             None => Type::Unit,
             Some(alternative) => {
@@ -410,11 +955,37 @@ impl<'interner> TypeChecker<'interner> {
                     err.add_context(context)
                 });
 
-                then_type
+                // Both branches are still checked, but only the taken one determines the type
+                if constant_condition == Some(false) {
+                    else_type
+                } else {
+                    then_type
+                }
             }
         }
     }
 
+    /// Warn about the branch of an `if` that can never be taken because its condition is constant
+    fn check_dead_branch(&mut self, if_expr: &expr::HirIfExpression, condition: bool) {
+        if !self.interner.type_check_options().warn_dead_branches {
+            return;
+        }
+
+        let dead_branch = if condition { if_expr.alternative } else { Some(if_expr.consequence) };
+        if let Some(branch) = dead_branch {
+            let span = self.interner.expr_span(&branch);
+            self.warnings.push(TypeCheckWarning::DeadBranch { condition, span });
+        }
+    }
+
+    /// The value of the given expression if it is a boolean literal
+    fn bool_literal(&self, expr_id: &ExprId) -> Option<bool> {
+        match self.interner.expression(expr_id) {
+            HirExpression::Literal(HirLiteral::Bool(value)) => Some(value),
+            _ => None,
+        }
+    }
+
     fn check_constructor(
         &mut self,
         constructor: expr::HirConstructorExpression,
@@ -477,6 +1048,10 @@ impl<'interner> TypeChecker<'interner> {
             self.errors.push(TypeCheckError::TypeAnnotationsNeeded {
                 span: self.interner.expr_span(&access.lhs),
             });
+        } else if let Type::Struct(..) = &lhs_type {
+            let span = access.rhs.span();
+            let field = access.rhs.0.contents;
+            self.errors.push(TypeCheckError::UnknownField { typ: lhs_type, field, span });
         } else if lhs_type != Type::Error {
             self.errors.push(TypeCheckError::Unstructured {
                 msg: format!("Type {lhs_type} has no member named {}", access.rhs),
@@ -526,7 +1101,7 @@ impl<'interner> TypeChecker<'interner> {
                 }
             }
             (Integer(comptime_x, sign_x, bit_width_x), Integer(comptime_y, sign_y, bit_width_y)) => {
-                if sign_x != sign_y {
+                if sign_x != sign_y && !self.allow_signedness_mix(*bit_width_x, *bit_width_y, op.location.span) {
                     return Err(format!("Integers must have the same signedness LHS is {sign_x:?}, RHS is {sign_y:?} "))
                 }
                 if bit_width_x != bit_width_y {
@@ -538,6 +1113,11 @@ impl<'interner> TypeChecker<'interner> {
             (Integer(..), FieldElement(..)) | ( FieldElement(..), Integer(..) ) => {
                 Err("Cannot use an integer and a Field in a binary operation, try converting the Field into an integer first".to_string())
             }
+            (Integer(comptime_x, Signedness::Unsigned, 1), Bool(comptime_y))
+            | (Bool(comptime_x), Integer(comptime_y, Signedness::Unsigned, 1)) => {
+                let comptime = comptime_x.and(comptime_y, op.location.span);
+                Ok(Bool(comptime))
+            }
             (Integer(..), typ) | (typ,Integer(..)) => {
                 Err(format!("Integer cannot be used with type {typ}"))
             }
@@ -584,7 +1164,7 @@ impl<'interner> TypeChecker<'interner> {
                 }
                 Err(format!("Unsupported types for comparison: {name_a} and {name_b}"))
             }
-            (String(x_size), String(y_size)) => {
+            (String(x_size), String(y_size)) if matches!(op.kind, Equal | NotEqual) => {
                 x_size.unify(y_size, op.location.span, &mut self.errors, || {
                     TypeCheckError::Unstructured {
                         msg: format!("Can only compare strings of the same length. Here LHS is of length {x_size}, and RHS is {y_size} "),
@@ -594,10 +1174,49 @@ impl<'interner> TypeChecker<'interner> {
 
                 Ok(Bool(CompTime::No(Some(op.location.span))))
             }
+            // Characters are ordered by their utf-8 byte
+            (Char, Char) => Ok(Bool(CompTime::No(Some(op.location.span)))),
             (lhs, rhs) => Err(format!("Unsupported types for comparison: {lhs} and {rhs}")),
         }
     }
 
+    /// A binary operation between an integer and a Field is reported along with the Field
+    /// operand and the integer type it should be cast to, so that the cast can be suggested.
+    /// Shifts are excluded as the amount to shift by follows its own rules.
+    fn witness_integer_mix(
+        &self,
+        infix_expr: &expr::HirInfixExpression,
+        lhs_type: &Type,
+        rhs_type: &Type,
+    ) -> Option<TypeCheckError> {
+        if infix_expr.operator.kind.is_shift() {
+            return None;
+        }
+
+        let (witness, sign, bits) = match (lhs_type.follow_bindings(), rhs_type.follow_bindings()) {
+            (Type::FieldElement(_), Type::Integer(_, sign, bits)) => (infix_expr.lhs, sign, bits),
+            (Type::Integer(_, sign, bits), Type::FieldElement(_)) => (infix_expr.rhs, sign, bits),
+            _ => return None,
+        };
+
+        let integer_type = Type::Integer(CompTime::No(None), sign, bits);
+        let span = self.interner.expr_span(&witness);
+        Some(TypeCheckError::WitnessIntegerMix { witness, integer_type, span })
+    }
+
+    /// With [`TypeCheckOptions::allow_signedness_mix`](super::TypeCheckOptions) set, a signed and
+    /// an unsigned integer of the same bit size may be used together as the signed type.
+    /// Returns whether the mix is accepted, warning if so.
+    fn allow_signedness_mix(&mut self, bit_width_x: u32, bit_width_y: u32, span: Span) -> bool {
+        if bit_width_x != bit_width_y || !self.interner.type_check_options().allow_signedness_mix {
+            return false;
+        }
+
+        let typ = Type::Integer(CompTime::No(None), Signedness::Signed, bit_width_x);
+        self.warnings.push(TypeCheckWarning::SignednessMix { typ, span });
+        true
+    }
+
     fn lookup_method(
         &mut self,
         object_type: Type,
@@ -609,11 +1228,10 @@ impl<'interner> TypeChecker<'interner> {
                 match self.interner.lookup_method(typ.borrow().id, method_name) {
                     Some(method_id) => Some(method_id),
                     None => {
-                        self.errors.push(TypeCheckError::Unstructured {
+                        self.errors.push(TypeCheckError::UnknownMethod {
+                            typ: object_type.clone(),
+                            method: method_name.to_string(),
                             span: self.interner.expr_span(expr_id),
-                            msg: format!(
-                                "No method named '{method_name}' found for type '{object_type}'",
-                            ),
                         });
                         None
                     }
@@ -628,9 +1246,10 @@ impl<'interner> TypeChecker<'interner> {
             other => match self.interner.lookup_primitive_method(other, method_name) {
                 Some(method_id) => Some(method_id),
                 None => {
-                    self.errors.push(TypeCheckError::Unstructured {
+                    self.errors.push(TypeCheckError::UnknownMethod {
+                        typ: other.clone(),
+                        method: method_name.to_string(),
                         span: self.interner.expr_span(expr_id),
-                        msg: format!("No method named '{method_name}' found for type '{other}'",),
                     });
                     None
                 }
@@ -638,7 +1257,122 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
-    fn bind_function_type(&mut self, function: Type, args: Vec<(Type, Span)>, span: Span) -> Type {
+    /// Matches the arguments of a call to a function by name against its parameters.
+    /// Named arguments, e.g. `foo(y: 2, x: 1)`, are reordered to follow the parameters and each
+    /// omitted parameter with a default value is given that value. The now positional call is
+    /// stored back in the interner. Any parameter left without an argument is reported as an
+    /// arity mismatch afterward.
+    ///
+    /// Returns false if a name could not be matched to a parameter. The arguments are then left
+    /// as written so that each of them is still type checked.
+    fn order_call_arguments(
+        &mut self,
+        expr_id: &ExprId,
+        mut call: HirCallExpression,
+    ) -> (HirCallExpression, bool) {
+        let names = call.argument_names.take();
+
+        let func_id = match self.interner.expression(&call.func) {
+            HirExpression::Ident(ident) => match &self.interner.definition(ident.id).kind {
+                DefinitionKind::Function(func_id) => Some(*func_id),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let func_id = match (func_id, &names) {
+            (Some(func_id), _) => func_id,
+            (None, None) => return (call, true),
+            (None, Some(_)) => {
+                let span = self.interner.expr_span(&call.func);
+                self.errors.push(TypeCheckError::NamedArgumentsOnNonFunction { span });
+                return (call, false);
+            }
+        };
+
+        let meta = self.interner.function_meta(&func_id);
+        let is_named = names.is_some();
+
+        let mut ordered = match names {
+            Some(names) => {
+                match self.order_named_arguments(func_id, &meta, names, &call.arguments) {
+                    Some(ordered) => ordered,
+                    None => return (call, false),
+                }
+            }
+            None if call.arguments.len() < meta.parameter_defaults.len() => {
+                let mut arguments = vecmap(&call.arguments, |argument| Some(*argument));
+                arguments.resize(meta.parameter_defaults.len(), None);
+                arguments
+            }
+            None => return (call, true),
+        };
+
+        // A positional call missing a required argument is left as written
+        // so that the arity mismatch reports the arguments actually given.
+        let missing_required = ordered
+            .iter()
+            .zip(&meta.parameter_defaults)
+            .any(|(argument, default)| argument.is_none() && default.is_none());
+        if !is_named && missing_required {
+            return (call, true);
+        }
+
+        // Each call is given its own copy of a default so that the types
+        // inferred for it at one call do not leak into another.
+        for (argument, default) in ordered.iter_mut().zip(&meta.parameter_defaults) {
+            if argument.is_none() {
+                *argument = default.map(|default| self.interner.copy_expression(&default));
+            }
+        }
+
+        call.arguments = ordered.into_iter().flatten().collect();
+        self.interner.replace_expr(expr_id, HirExpression::Call(call.clone()));
+        (call, true)
+    }
+
+    /// Places each named argument at the position of the parameter of the same name.
+    /// Parameters without a named argument are left as `None`. Returns None after reporting
+    /// every name which is unknown or given more than once.
+    fn order_named_arguments(
+        &mut self,
+        func_id: FuncId,
+        meta: &FuncMeta,
+        names: Vec<Ident>,
+        arguments: &[ExprId],
+    ) -> Option<Vec<Option<ExprId>>> {
+        let parameters = vecmap(&meta.parameters.0, |param| {
+            get_param_name(&param.0, self.interner).map(ToOwned::to_owned)
+        });
+
+        let mut ordered = vec![None; parameters.len()];
+        let mut all_matched = true;
+        for (name, argument) in names.into_iter().zip(arguments) {
+            let span = name.0.span();
+            let name = name.0.contents;
+
+            match parameters.iter().position(|param| param.as_ref() == Some(&name)) {
+                Some(index) if ordered[index].is_some() => {
+                    self.errors.push(TypeCheckError::DuplicateArgumentName { name, span });
+                    all_matched = false;
+                }
+                Some(index) => ordered[index] = Some(*argument),
+                None => {
+                    let function = self.interner.function_name(&func_id).to_owned();
+                    self.errors.push(TypeCheckError::UnknownArgumentName { function, name, span });
+                    all_matched = false;
+                }
+            }
+        }
+        all_matched.then_some(ordered)
+    }
+
+    fn bind_function_type(
+        &mut self,
+        function: Type,
+        args: Vec<(Type, ExprId, Span)>,
+        span: Span,
+    ) -> Type {
         // Could do a single unification for the entire function type, but matching beforehand
         // lets us issue a more precise error on the individual argument that fails to type check.
         match function {
@@ -648,7 +1382,7 @@ impl<'interner> TypeChecker<'interner> {
                 }
 
                 let ret = self.interner.next_type_variable();
-                let args = vecmap(args, |(arg, _)| arg);
+                let args = vecmap(args, |(arg, _, _)| arg);
                 let expected = Type::Function(args, Box::new(ret.clone()));
                 *binding.borrow_mut() = TypeBinding::Bound(expected);
 
@@ -672,7 +1406,20 @@ impl<'interner> TypeChecker<'interner> {
                     return Type::Error;
                 }
 
-                for (param, (arg, arg_span)) in parameters.iter().zip(args) {
+                for (param, (arg, arg_id, arg_span)) in parameters.iter().zip(args) {
+                    if let Err(error) = types_compatible(&arg, param, arg_span) {
+                        self.errors.push(error);
+                        continue;
+                    }
+
+                    // A u1 and a bool are accepted for each other but can never be unified,
+                    // so the argument is converted to the parameter type explicitly instead
+                    if is_u1_and_bool(&arg, param) {
+                        self.insert_cast(&arg_id, param.follow_bindings());
+                        continue;
+                    }
+
+                    // Compatible types may still need their type variables bound to each other
                     arg.make_subtype_of(param, arg_span, &mut self.errors, || {
                         TypeCheckError::TypeMismatch {
                             expected_typ: param.to_string(),
@@ -682,6 +1429,13 @@ impl<'interner> TypeChecker<'interner> {
                     });
                 }
 
+                // Lengths such as `N - 1` can only be evaluated once the generics are bound
+                let function = Type::Function(parameters, ret.clone());
+                if let Some((lhs, op, rhs)) = function.invalid_length_operation() {
+                    self.errors.push(TypeCheckError::InvalidArrayLength { lhs, op, rhs, span });
+                    return Type::Error;
+                }
+
                 *ret
             }
             Type::Error => Type::Error,
@@ -706,10 +1460,22 @@ impl<'interner> TypeChecker<'interner> {
     ) -> Result<Type, TypeCheckError> {
         let make_error = move |msg| TypeCheckError::Unstructured { msg, span };
 
+        // Shifting by a value only known at runtime cannot be cheaply constrained in a circuit
+        if op.kind.is_shift() && !self.is_unconstrained() && is_runtime_value(rhs_type) {
+            let msg = format!("The amount to shift by must be known at compile-time, found {rhs_type}. Shifting by a runtime value is only possible in unconstrained functions");
+            return Err(make_error(msg));
+        }
+
         if op.kind.is_comparator() {
             return self.comparator_operand_type_rules(lhs_type, rhs_type, op).map_err(make_error);
         }
 
+        if op.kind.is_shift() {
+            if let Some(result) = shift_operand_type_rules(lhs_type, rhs_type, span) {
+                return result.map_err(make_error);
+            }
+        }
+
         use Type::*;
         match (lhs_type, rhs_type)  {
             // An error type on either side will always return an error
@@ -723,6 +1489,12 @@ impl<'interner> TypeChecker<'interner> {
                     return self.infix_operand_type_rules(binding, op, other, span);
                 }
 
+                if self.interner.type_check_options().require_explicit_int_coercion {
+                    if let Integer(..) = other.follow_bindings() {
+                        return Err(make_error(format!("An integer literal must be cast to {other} explicitly before it is used with it in a binary operation, e.g. `3 as {other}`")));
+                    }
+                }
+
                 if op.is_bitwise() && (other.is_bindable() || other.is_field()) {
                     let other = other.follow_bindings();
 
@@ -761,7 +1533,11 @@ impl<'interner> TypeChecker<'interner> {
             }
             (Integer(comptime_x, sign_x, bit_width_x), Integer(comptime_y, sign_y, bit_width_y)) => {
                 if sign_x != sign_y {
-                    return Err(make_error(format!("Integers must have the same signedness LHS is {sign_x:?}, RHS is {sign_y:?} ")))
+                    if !self.allow_signedness_mix(*bit_width_x, *bit_width_y, span) {
+                        return Err(make_error(format!("Integers must have the same signedness LHS is {sign_x:?}, RHS is {sign_y:?} ")))
+                    }
+                    let comptime = comptime_x.and(comptime_y, op.location.span);
+                    return Ok(Integer(comptime, Signedness::Signed, *bit_width_x));
                 }
                 if bit_width_x != bit_width_y {
                     return Err(make_error(format!("Integers must have the same bit width LHS is {bit_width_x}, RHS is {bit_width_y} ")))
@@ -772,6 +1548,14 @@ impl<'interner> TypeChecker<'interner> {
             (Integer(..), FieldElement(..)) | (FieldElement(..), Integer(..)) => {
                 Err(make_error("Cannot use an integer and a Field in a binary operation, try converting the Field into an integer".to_string()))
             }
+            // A u1 may be used as a bool, but only with the logical operators
+            (Integer(comptime_x, Signedness::Unsigned, 1), Bool(comptime_y))
+            | (Bool(comptime_x), Integer(comptime_y, Signedness::Unsigned, 1)) => {
+                if !matches!(op.kind, BinaryOpKind::And | BinaryOpKind::Or | BinaryOpKind::Xor) {
+                    return Err(make_error(format!("The operator {} cannot be used on booleans, only `&`, `|` and `^` are supported", op.kind)));
+                }
+                Ok(Bool(comptime_x.and(comptime_y, op.location.span)))
+            }
             (Integer(..), typ) | (typ,Integer(..)) => {
                 Err(make_error(format!("Integer cannot be used with type {typ}")))
             }
@@ -779,6 +1563,8 @@ impl<'interner> TypeChecker<'interner> {
             (Array(..), _) | (_, Array(..)) => Err(make_error("Arrays cannot be used in an infix operation".to_string())),
             (Struct(..), _) | (_, Struct(..)) => Err(make_error("Structs cannot be used in an infix operation".to_string())),
             (Tuple(_), _) | (_, Tuple(_)) => Err(make_error("Tuples cannot be used in an infix operation".to_string())),
+            (String(_), _) | (_, String(_)) => Err(make_error("Strings cannot be used in an infix operation".to_string())),
+            (Char, _) | (_, Char) => Err(make_error("Characters cannot be used in an infix operation, only compared".to_string())),
 
             (Unit, _) | (_,Unit) => Ok(Unit),
 
@@ -791,25 +1577,171 @@ impl<'interner> TypeChecker<'interner> {
                 Ok(FieldElement(comptime))
             }
 
-            (Bool(comptime_x), Bool(comptime_y)) => Ok(Bool(comptime_x.and(comptime_y, op.location.span))),
+            // Booleans only support the logical operators `&`, `|` and `^`
+            (Bool(comptime_x), Bool(comptime_y)) => {
+                if !matches!(op.kind, BinaryOpKind::And | BinaryOpKind::Or | BinaryOpKind::Xor) {
+                    return Err(make_error(format!("The operator {} cannot be used on booleans, only `&`, `|` and `^` are supported", op.kind)));
+                }
+                Ok(Bool(comptime_x.and(comptime_y, op.location.span)))
+            }
 
             (lhs, rhs) => Err(make_error(format!("Unsupported types for binary operation: {lhs} and {rhs}"))),
         }
     }
 }
 
-fn prefix_operand_type_rules(op: &crate::UnaryOp, rhs_type: &Type) -> Result<Type, String> {
-    match op {
-        crate::UnaryOp::Minus => {
-            if !matches!(rhs_type, Type::Integer(..) | Type::Error) {
-                return Err("Only Integers can be used in a Minus expression".to_string());
+/// Checks whether an argument of type `arg` would be accepted for a parameter of type `param`,
+/// without binding any type variables. This uses the same subtyping rules as binding the
+/// argument, applied to copies of both types.
+pub fn types_compatible(arg: &Type, param: &Type, span: Span) -> Result<(), TypeCheckError> {
+    // A u1 and a bool are never unified, but each is accepted where the other is expected
+    if is_u1_and_bool(arg, param) || arg.could_be_subtype_of(param) {
+        return Ok(());
+    }
+
+    let mismatch = TypeCheckError::TypeMismatch {
+        expected_typ: param.to_string(),
+        expr_typ: arg.to_string(),
+        expr_span: span,
+    };
+
+    match (arg.follow_bindings(), param.follow_bindings()) {
+        _ if is_variable_to_fixed_array(arg, param) => {
+            Err(TypeCheckError::VariableArrayForFixedParameter {
+                expected_typ: param.to_string(),
+                expr_typ: arg.to_string(),
+                span,
+            })
+        }
+        // Point out which part of the array differs rather than only the whole array types
+        (Type::Array(arg_length, arg_element), Type::Array(param_length, param_element)) => {
+            match (arg_length.evaluate_to_u64(), param_length.evaluate_to_u64()) {
+                (Some(found), Some(expected)) if found != expected => {
+                    Err(TypeCheckError::ArrayLengthMismatch { expected, found, span })
+                }
+                _ => match types_compatible(&arg_element, &param_element, span) {
+                    Err(error) => Err(error.add_context("the array element types differ")),
+                    Ok(()) => Err(mismatch),
+                },
             }
         }
-        crate::UnaryOp::Not => {
-            if !matches!(rhs_type, Type::Integer(..) | Type::Bool(_) | Type::Error) {
-                return Err("Only Integers or Bool can be used in a Not expression".to_string());
+        _ => Err(mismatch),
+    }
+}
+
+/// True if one type is a `u1` and the other a `bool`
+fn is_u1_and_bool(a: &Type, b: &Type) -> bool {
+    let is_u1 =
+        |typ: &Type| matches!(typ.follow_bindings(), Type::Integer(_, Signedness::Unsigned, 1));
+    let is_bool = |typ: &Type| matches!(typ.follow_bindings(), Type::Bool(_));
+    (is_u1(a) && is_bool(b)) || (is_bool(a) && is_u1(b))
+}
+
+/// True if `arg` is an array whose length is generic while `param` is an array of a known length.
+/// The length of such an argument cannot be verified to match the parameter.
+fn is_variable_to_fixed_array(arg: &Type, param: &Type) -> bool {
+    match (arg.follow_bindings(), param.follow_bindings()) {
+        (Type::Array(arg_length, _), Type::Array(param_length, _)) => {
+            matches!(*arg_length, Type::NamedGeneric(..))
+                && param_length.evaluate_to_u64().is_some()
+        }
+        _ => false,
+    }
+}
+
+/// True if the given type is a numeric type whose value is only known at runtime.
+/// Polymorphic integers which may still be comptime are not considered runtime values.
+fn is_runtime_value(typ: &Type) -> bool {
+    match typ.follow_bindings() {
+        Type::FieldElement(comptime) | Type::Integer(comptime, ..) => !comptime.is_comp_time(),
+        _ => false,
+    }
+}
+
+/// False if `typ` is an integer type which cannot represent the given value
+fn fits_in_type(value: u128, typ: &Type) -> bool {
+    let (sign, bit_size) = match typ.follow_bindings() {
+        Type::Integer(_, sign, bit_size) => (sign, bit_size),
+        _ => return true,
+    };
+
+    let value_bits = match sign {
+        Signedness::Unsigned => bit_size,
+        Signedness::Signed => bit_size.saturating_sub(1),
+    };
+    value_bits >= 128 || value < 1 << value_bits
+}
+
+/// Shifting a sized integer only requires the amount to shift by to be an unsigned integer,
+/// not one of the same bit width. Returns None if the shifted value is not a sized integer,
+/// in which case the usual rules for binary operations apply.
+fn shift_operand_type_rules(
+    lhs_type: &Type,
+    rhs_type: &Type,
+    span: Span,
+) -> Option<Result<Type, String>> {
+    let (comptime, bit_size) = match lhs_type.follow_bindings() {
+        Type::Integer(_, Signedness::Signed, _) => {
+            return Some(Err(format!("Only unsigned integers can be shifted, found {lhs_type}")))
+        }
+        Type::Integer(comptime, Signedness::Unsigned, bit_size) => (comptime, bit_size),
+        _ => return None,
+    };
+
+    let result = match rhs_type.follow_bindings() {
+        Type::Error => Ok(Type::Error),
+        Type::Integer(rhs_comptime, Signedness::Unsigned, _) => {
+            Ok(Type::Integer(comptime.and(&rhs_comptime, span), Signedness::Unsigned, bit_size))
+        }
+        Type::PolymorphicInteger(..) | Type::TypeVariable(_) => {
+            // Literal shift amounts default to the type of the value being shifted
+            let msg =
+                format!("The amount to shift by must be an unsigned integer, found {rhs_type}");
+            let mut errors = vec![];
+            rhs_type.unify(lhs_type, span, &mut errors, || TypeCheckError::Unstructured {
+                msg: msg.clone(),
+                span,
+            });
+            if errors.is_empty() {
+                Ok(lhs_type.clone())
+            } else {
+                Err(msg)
             }
         }
+        _ => Err(format!("The amount to shift by must be an unsigned integer, found {rhs_type}")),
+    };
+    Some(result)
+}
+
+/// Negation is allowed on fields and signed integers. Unsigned integers are rejected
+/// rather than silently wrapping around their modulus.
+fn prefix_operand_type_rules(
+    op: &crate::UnaryOp,
+    rhs_type: &Type,
+    span: Span,
+) -> Result<Type, TypeCheckError> {
+    let mismatch = |expected_typ: &str| TypeCheckError::TypeMismatch {
+        expected_typ: expected_typ.to_string(),
+        expr_typ: rhs_type.to_string(),
+        expr_span: span,
+    };
+
+    match op {
+        crate::UnaryOp::Minus => match rhs_type.follow_bindings() {
+            Type::Integer(_, Signedness::Unsigned, _) => Err(TypeCheckError::Unstructured {
+                msg: format!("Cannot negate a value of unsigned type {rhs_type}"),
+                span,
+            }),
+            Type::Integer(..)
+            | Type::FieldElement(_)
+            | Type::PolymorphicInteger(..)
+            | Type::Constant(_)
+            | Type::Error => Ok(rhs_type.clone()),
+            _ => Err(mismatch("Field or a signed integer")),
+        },
+        crate::UnaryOp::Not => match rhs_type.follow_bindings() {
+            Type::Integer(..) | Type::Bool(_) | Type::Error => Ok(rhs_type.clone()),
+            _ => Err(mismatch("bool or an integer")),
+        },
     }
-    Ok(rhs_type.clone())
 }
@@ -14,23 +14,96 @@ mod errors;
 mod expr;
 mod stmt;
 
-pub use errors::TypeCheckError;
-use noirc_errors::Span;
+pub use errors::{TypeCheckError, TypeCheckWarning};
+pub use expr::types_compatible;
+use iter_extended::vecmap;
+use noirc_errors::{Location, Span};
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    hir_def::types::ExportedType,
     node_interner::{ExprId, FuncId, NodeInterner, StmtId},
     Type,
 };
 
+/// Settings for the optional checks performed while type checking.
+/// By default every optional check is disabled.
+#[derive(Debug, Clone, Default)]
+pub struct TypeCheckOptions {
+    /// Warn when an integer literal larger than this value is implicitly given the Field type.
+    pub large_constant_threshold: Option<u128>,
+    /// Warn when a Field is cast to an integer type, which truncates any value too large for it.
+    pub warn_field_to_integer_casts: bool,
+    /// Record each expression of a function whose type inference left unknown,
+    /// see [`TypeCheckOutput::unresolved_types`].
+    pub report_unresolved_types: bool,
+    /// Error when a loop over a range with literal bounds would be unrolled into more than
+    /// this many iterations.
+    pub max_loop_iterations: Option<u64>,
+    /// Warn about branches of an `if` which are never taken because its condition is a literal.
+    pub warn_dead_branches: bool,
+    /// Warn when a value is cast to the type it already has.
+    pub warn_redundant_casts: bool,
+    /// Accept loop ranges only known at runtime in constrained functions, as is done for
+    /// unconstrained functions. These loops cannot be unrolled by the default backend.
+    /// `while` loops remain limited to unconstrained functions.
+    pub allow_dynamic_loops: bool,
+    /// Accept binary operations between a signed and an unsigned integer of the same bit size,
+    /// treating both as the signed type. A warning is still reported for each such operation.
+    pub allow_signedness_mix: bool,
+    /// Reject arithmetic between a sized integer and an integer literal, or a variable still
+    /// holding one, unless the literal is first cast to the integer type, as in `x + 3 as u8`.
+    pub require_explicit_int_coercion: bool,
+}
+
+/// The diagnostics produced by type checking a single function or global.
+#[derive(Debug, Default)]
+pub struct TypeCheckOutput {
+    pub errors: Vec<TypeCheckError>,
+    pub warnings: Vec<TypeCheckWarning>,
+    unresolved_types: Vec<(ExprId, Type)>,
+}
+
+impl TypeCheckOutput {
+    /// The expressions whose type still contained an unbound type variable or an error
+    /// once checking finished. This is only collected for functions, and only when
+    /// [`TypeCheckOptions::report_unresolved_types`] is set.
+    pub fn unresolved_types(&self) -> &[(ExprId, Type)] {
+        &self.unresolved_types
+    }
+}
+
 pub struct TypeChecker<'interner> {
     current_function: Option<FuncId>,
     interner: &'interner mut NodeInterner,
     errors: Vec<TypeCheckError>,
+    warnings: Vec<TypeCheckWarning>,
+
+    /// Integer literals over the large constant threshold, along with their types.
+    /// Whether each defaults to a Field is only known after the whole body is checked.
+    large_constants: Vec<(Span, Type)>,
+
+    /// Integer literals along with their types. A literal's type may only be fixed by a
+    /// later use, so whether its value fits is checked after the whole body.
+    integer_literals: Vec<IntegerLiteral>,
+
+    /// True while checking the body of a lambda, where a `return` is not allowed.
+    in_lambda: bool,
+}
+
+/// An integer literal which is checked to fit the range of its type, see
+/// [`TypeChecker::check_literal_ranges`]
+struct IntegerLiteral {
+    value: u128,
+    /// True if the literal is the operand of a unary minus, as in `-128`
+    negated: bool,
+    typ: Type,
+    span: Span,
 }
 
 /// Type checks a function and assigns the
 /// appropriate types to expressions in a side table
-pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<TypeCheckError> {
+pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> TypeCheckOutput {
     let meta = interner.function_meta(&func_id);
     let declared_return_type = meta.return_type().clone();
     let can_ignore_ret = meta.can_ignore_return_type();
@@ -38,7 +111,14 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
     let function_body = interner.function(&func_id);
     let function_body_id = function_body.as_expr();
 
-    let mut type_checker = TypeChecker::new(func_id, interner);
+    let mut type_checker = TypeChecker::new(Some(func_id), interner);
+
+    // Default parameter values are checked once here rather than at each call which uses them
+    for (param, default) in meta.parameters.0.iter().zip(&meta.parameter_defaults) {
+        if let Some(default) = default {
+            type_checker.check_parameter_default(default, &param.1);
+        }
+    }
 
     // Bind each parameter to its annotated type.
     // This is locally obvious, but it must be bound here so that the
@@ -47,19 +127,21 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
         type_checker.bind_pattern(&param.0, param.1);
     }
 
-    let (function_last_type, mut errors) = type_checker.check_function_body(function_body_id);
+    let (function_last_type, mut output) = type_checker.check_function_body(function_body_id);
 
     // Go through any delayed type checking errors to see if they are resolved, or error otherwise.
     for type_check_fn in interner.take_delayed_type_check_functions() {
         if let Err(error) = type_check_fn() {
-            errors.push(error);
+            output.errors.push(error);
         }
     }
 
-    // Check declared return type and actual return type
+    // Check declared return type and actual return type.
+    // A body ending in a `return` has a fresh type, which this binds to the declared one.
     if !can_ignore_ret {
         let func_span = interner.expr_span(function_body_id); // XXX: We could be more specific and return the span of the last stmt, however stmts do not have spans yet
-        function_last_type.make_subtype_of(&declared_return_type, func_span, &mut errors, || {
+        let errors = &mut output.errors;
+        function_last_type.make_subtype_of(&declared_return_type, func_span, errors, || {
             TypeCheckError::TypeMismatch {
                 expected_typ: declared_return_type.to_string(),
                 expr_typ: function_last_type.to_string(),
@@ -68,23 +150,206 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
         });
     }
 
+    if interner.type_check_options().report_unresolved_types {
+        output.unresolved_types =
+            interner.expressions_in(func_id).filter(|(_, typ)| is_unresolved(typ)).collect();
+    }
+
+    output
+}
+
+/// True if inference left any part of this type unknown, or gave up on it with an error
+fn is_unresolved(typ: &Type) -> bool {
+    match typ.follow_bindings() {
+        Type::Error | Type::TypeVariable(_) => true,
+        Type::Array(length, element) => is_unresolved(&length) || is_unresolved(&element),
+        Type::String(length) => is_unresolved(&length),
+        Type::Vec(element) => is_unresolved(&element),
+        Type::Tuple(fields) => fields.iter().any(is_unresolved),
+        Type::Struct(_, args) => args.iter().any(is_unresolved),
+        Type::Function(args, ret) => args.iter().any(is_unresolved) || is_unresolved(&ret),
+        Type::InfixExpr(lhs, _, rhs) => is_unresolved(&lhs) || is_unresolved(&rhs),
+        Type::FieldElement(_)
+        | Type::Integer(..)
+        | Type::PolymorphicInteger(..)
+        | Type::Bool(_)
+        | Type::Char
+        | Type::Unit
+        | Type::NamedGeneric(..)
+        | Type::Constant(_)
+        | Type::Forall(..) => false,
+    }
+}
+
+/// Checks that the signature of a program's `main` function can be represented in its ABI.
+/// Each parameter and the return value of `main` must have a concrete type of a known size,
+/// so generic and variable-length types are rejected.
+///
+/// A unit return type is still allowed: it means `main` has no public outputs, which is how
+/// most existing programs are written, with their results constrained by `assert` instead.
+pub fn type_check_main(interner: &NodeInterner, func_id: FuncId) -> Vec<TypeCheckError> {
+    let meta = interner.function_meta(&func_id);
+    let mut errors = vec![];
+
+    for param in meta.parameters.iter() {
+        if !is_abi_compatible(&param.1) {
+            let span = param.0.span();
+            errors.push(TypeCheckError::TypeCannotBeUsed {
+                typ: param.1.clone(),
+                place: "parameter of main",
+                span,
+            });
+        }
+    }
+
+    let return_type = meta.return_type();
+    if *return_type != Type::Unit && !is_abi_compatible(return_type) {
+        errors.push(TypeCheckError::TypeCannotBeUsed {
+            typ: return_type.clone(),
+            place: "return type of main",
+            span: meta.name.location.span,
+        });
+    }
+
     errors
 }
 
+/// The types of every expression in a program, see [`export_typed_program`]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ExportedProgram {
+    pub functions: Vec<ExportedFunction>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ExportedFunction {
+    pub name: String,
+    pub file: usize,
+    pub expressions: Vec<ExportedExpression>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ExportedExpression {
+    pub start: u32,
+    pub end: u32,
+    #[serde(rename = "type")]
+    pub typ: ExportedType,
+}
+
+/// Exports the type given to each expression of each function with a body, for tools which
+/// need the results of type checking without depending on the NodeInterner. Expressions
+/// which failed to type check are exported with the error type.
+pub fn export_typed_program(interner: &NodeInterner) -> ExportedProgram {
+    let mut functions: Vec<_> = interner
+        .function_ids()
+        .map(|func_id| (func_id, interner.function_meta(&func_id)))
+        .filter(|(_, meta)| meta.has_body)
+        .collect();
+    functions.sort_by_key(|(_, meta)| (meta.location.file.as_usize(), meta.location.span.start()));
+
+    let functions = vecmap(functions, |(func_id, meta)| {
+        let expressions = vecmap(interner.expressions_in(func_id), |(expr_id, typ)| {
+            let span = interner.expr_span(&expr_id);
+            ExportedExpression { start: span.start(), end: span.end(), typ: typ.as_exported_type() }
+        });
+
+        ExportedFunction {
+            name: interner.function_name(&func_id).to_owned(),
+            file: meta.location.file.as_usize(),
+            expressions,
+        }
+    });
+
+    ExportedProgram { functions }
+}
+
+/// Returns the type of the innermost expression at the given location, as an editor would show
+/// for the position of the cursor. This is None if there is no expression there, or if it was
+/// never type checked.
+pub fn type_at_span(interner: &NodeInterner, location: Location) -> Option<Type> {
+    interner.expression_at(location).and_then(|expr_id| interner.try_expr_type(expr_id))
+}
+
+/// True if values of this type may be passed to or returned from `main`
+fn is_abi_compatible(typ: &Type) -> bool {
+    match typ.follow_bindings() {
+        Type::FieldElement(_)
+        | Type::Integer(..)
+        | Type::Bool(_)
+        | Type::Char
+        | Type::PolymorphicInteger(..)
+        | Type::Error => true,
+        Type::Array(length, element) => {
+            length.evaluate_to_u64().is_some() && is_abi_compatible(&element)
+        }
+        Type::String(length) => length.evaluate_to_u64().is_some(),
+        Type::Struct(def, args) => def.borrow().get_fields(&args).values().all(is_abi_compatible),
+        Type::Unit
+        | Type::Tuple(_)
+        | Type::TypeVariable(_)
+        | Type::NamedGeneric(..)
+        | Type::Function(..)
+        | Type::Vec(_)
+        | Type::Forall(..)
+        | Type::Constant(_)
+        | Type::InfixExpr(..) => false,
+    }
+}
+
 impl<'interner> TypeChecker<'interner> {
-    fn new(current_function: FuncId, interner: &'interner mut NodeInterner) -> Self {
-        Self { current_function: Some(current_function), interner, errors: vec![] }
+    /// A checker for the body of the given function, or for a global if there is none
+    fn new(current_function: Option<FuncId>, interner: &'interner mut NodeInterner) -> Self {
+        Self {
+            current_function,
+            interner,
+            errors: vec![],
+            warnings: vec![],
+            large_constants: vec![],
+            integer_literals: vec![],
+            in_lambda: false,
+        }
+    }
+
+    fn check_parameter_default(&mut self, default: &ExprId, param_type: &Type) {
+        let default_type = self.check_expression(default);
+        let span = self.interner.expr_span(default);
+
+        self.make_subtype_of(&default_type, param_type, span, || TypeCheckError::TypeMismatch {
+            expected_typ: param_type.to_string(),
+            expr_typ: default_type.to_string(),
+            expr_span: span,
+        });
+        self.check_constant_fits(default, param_type);
     }
 
-    fn check_function_body(mut self, body: &ExprId) -> (Type, Vec<TypeCheckError>) {
+    fn check_function_body(mut self, body: &ExprId) -> (Type, TypeCheckOutput) {
         let body_type = self.check_expression(body);
-        (body_type, self.errors)
+        (body_type, self.finish())
     }
 
-    pub fn check_global(id: &StmtId, interner: &'interner mut NodeInterner) -> Vec<TypeCheckError> {
-        let mut this = Self { current_function: None, interner, errors: vec![] };
+    pub fn check_global(id: &StmtId, interner: &'interner mut NodeInterner) -> TypeCheckOutput {
+        let mut this = Self::new(None, interner);
         this.check_statement(id);
-        this.errors
+
+        // Checks delayed until the global is resolved must be run before the next function
+        // is checked, otherwise their errors would be reported for that function instead
+        for type_check_fn in this.interner.take_delayed_type_check_functions() {
+            if let Err(error) = type_check_fn() {
+                this.errors.push(error);
+            }
+        }
+        this.finish()
+    }
+
+    /// Report any warnings which depend on types that were still being inferred
+    /// while the body was checked.
+    fn finish(mut self) -> TypeCheckOutput {
+        for (span, typ) in self.large_constants.drain(..) {
+            if matches!(typ.follow_bindings(), Type::PolymorphicInteger(..)) {
+                self.warnings.push(TypeCheckWarning::LargeConstantPromotedToField { span });
+            }
+        }
+        self.check_literal_ranges();
+        TypeCheckOutput { errors: self.errors, warnings: self.warnings, ..Default::default() }
     }
 
     fn is_unconstrained(&self) -> bool {
@@ -120,26 +385,35 @@ impl<'interner> TypeChecker<'interner> {
 /// We can either build a test apparatus or pass raw code through the resolver
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
+    use std::rc::Rc;
 
+    use acvm::FieldElement;
     use fm::FileId;
     use iter_extended::vecmap;
     use noirc_errors::{Location, Span};
 
+    use super::{
+        errors::{TypeCheckError, TypeCheckWarning},
+        types_compatible, TypeCheckOptions, TypeCheckOutput,
+    };
     use crate::graph::CrateId;
     use crate::hir::def_map::{ModuleData, ModuleId, ModuleOrigin};
     use crate::hir::resolution::import::PathResolutionError;
     use crate::hir_def::expr::HirIdent;
     use crate::hir_def::stmt::HirLetStatement;
     use crate::hir_def::stmt::HirPattern::Identifier;
-    use crate::hir_def::types::Type;
+    use crate::hir_def::types::{ExportedType, Type};
     use crate::hir_def::{
-        expr::{HirBinaryOp, HirBlockExpression, HirExpression, HirInfixExpression},
+        expr::{
+            HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirExpression, HirInfixExpression,
+            HirLiteral,
+        },
         function::{FuncMeta, HirFunction, Param},
         stmt::HirStatement,
     };
+    use crate::monomorphization::{errors::MonomorphizationError, monomorphize};
     use crate::node_interner::{DefinitionKind, FuncId, NodeInterner};
-    use crate::BinaryOpKind;
     use crate::{
         hir::{
             def_map::{CrateDefMap, LocalModuleId, ModuleDefId},
@@ -147,6 +421,9 @@ mod test {
         },
         parse_program, FunctionKind, Path,
     };
+    use crate::{
+        BinaryOpKind, BinaryTypeOperator, CompTime, Shared, Signedness, TypeBinding, TypeVariableId,
+    };
 
     #[test]
     fn basic_let() {
@@ -218,14 +495,70 @@ mod test {
                 Param(Identifier(y), Type::field(None), noirc_abi::AbiVisibility::Private),
             ]
             .into(),
+            parameter_defaults: vec![None, None],
             return_visibility: noirc_abi::AbiVisibility::Private,
             return_distinctness: noirc_abi::AbiDistinctness::DuplicationAllowed,
             has_body: true,
         };
         interner.push_fn_meta(func_meta, func_id);
 
-        let errors = super::type_check_func(&mut interner, func_id);
-        assert!(errors.is_empty());
+        let output = super::type_check_func(&mut interner, func_id);
+        assert!(output.errors.is_empty());
+    }
+
+    #[test]
+    fn delayed_checks_in_globals() {
+        // global X: Field = 5 % 3;
+        let mut interner = NodeInterner::default();
+        let file = FileId::default();
+        let location = Location::new(Span::default(), file);
+
+        let literal = |interner: &mut NodeInterner, value: u128| {
+            let literal = HirLiteral::Integer(FieldElement::from(value));
+            let expr_id = interner.push_expr(HirExpression::Literal(literal));
+            interner.push_expr_location(expr_id, Span::single_char(0), file);
+            expr_id
+        };
+        let lhs = literal(&mut interner, 5);
+        let rhs = literal(&mut interner, 3);
+
+        let operator = HirBinaryOp { location, kind: BinaryOpKind::Modulo };
+        let infix = HirInfixExpression { lhs, operator, rhs };
+        let expression = interner.push_expr(HirExpression::Infix(infix));
+        interner.push_expr_location(expression, Span::single_char(0), file);
+
+        let x_id = interner.push_definition("X".into(), false, DefinitionKind::Local(None));
+        let let_stmt = HirLetStatement {
+            pattern: Identifier(HirIdent { id: x_id, location }),
+            r#type: Type::field(None),
+            expression,
+        };
+        let stmt_id = interner.push_stmt(HirStatement::Let(let_stmt));
+
+        // The modulo only becomes a Field modulo once the annotation is applied
+        let output = super::TypeChecker::check_global(&stmt_id, &mut interner);
+        assert_eq!(output.errors.len(), 1);
+        assert!(
+            matches!(&output.errors[0], TypeCheckError::Unstructured { msg, .. } if msg.contains("modulo"))
+        );
+        assert!(interner.take_delayed_type_check_functions().is_empty());
+    }
+
+    #[test]
+    fn empty_array_literal() {
+        // The parser rejects `[]` in source programs, so the literal is built by hand here
+        let mut interner = NodeInterner::default();
+        let array = HirLiteral::Array(HirArrayLiteral::Standard(vec![]));
+        let expr_id = interner.push_expr(HirExpression::Literal(array));
+        interner.push_expr_location(expr_id, Span::single_char(0), FileId::default());
+
+        let mut type_checker = super::TypeChecker::new(Some(FuncId::dummy_id()), &mut interner);
+        let typ = type_checker.check_expression(&expr_id);
+
+        let expected = Type::Array(Box::new(Type::Constant(0)), Box::new(Type::field(None)));
+        type_checker.unify(&typ, &expected, Span::single_char(0), || unreachable!());
+        assert_eq!(type_checker.errors, vec![]);
+        assert_eq!(typ.follow_bindings(), expected);
     }
 
     #[test]
@@ -241,6 +574,30 @@ mod test {
         type_check_src_code(src, vec![String::from("main")]);
     }
 
+    #[test]
+    fn let_annotations() {
+        // An annotated binding takes the annotated type, an unannotated one the initializer's type
+        let src = r#"
+            fn main(a : u16) -> pub u16 {
+                let x : u16 = 3;
+                let y = a;
+                x + y
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(a : u16) {
+                let x : u8 = a;
+                let y : u8 = 3;
+                let _z : u16 = y;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::TypeMismatch { .. })));
+    }
+
     #[test]
     fn basic_index_expr() {
         let src = r#"
@@ -252,6 +609,26 @@ mod test {
 
         type_check_src_code(src, vec![String::from("main")]);
     }
+
+    #[test]
+    fn block_expressions() {
+        let src = r#"
+            fn main(x : Field) -> pub Field {
+                let y = { let a = 1; a + 2 };
+                let z = { { x } };
+                y + z
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : Field) {
+                let _y: bool = { let a = x; a + 2 };
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+    }
     #[test]
     fn basic_call_expr() {
         let src = r#"
@@ -266,6 +643,207 @@ mod test {
 
         type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
     }
+
+    #[test]
+    fn named_call_arguments() {
+        let src = r#"
+            fn main(a : u8, b : bool) -> pub u8 {
+                foo(flag: b, x: a)
+            }
+
+            fn foo(x : u8, flag : bool) -> u8 {
+                if flag { x } else { 0 }
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
+
+        // Calls with a name error are not also reported as missing an argument, but
+        // the arguments which could not be matched are still type checked
+        let src = r#"
+            fn main(a : u8, b : bool) {
+                let _a = foo(x: a, y: b);
+                let _b = foo(x: a, x: a);
+                let _c = foo(x: a, z: b + 1);
+                let f = foo;
+                let _d = f(x: a, flag: b);
+            }
+
+            fn foo(x : u8, flag : bool) -> u8 {
+                if flag { x } else { 0 }
+            }
+        "#;
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("main"), String::from("foo")]);
+        assert_eq!(errors.len(), 5);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::UnknownArgumentName { function, name, .. }
+                if function == "foo" && name == "y"
+        ));
+        assert!(
+            matches!(&errors[1], TypeCheckError::DuplicateArgumentName { name, .. } if name == "x")
+        );
+        assert!(matches!(
+            &errors[2],
+            TypeCheckError::UnknownArgumentName { name, .. } if name == "z"
+        ));
+        assert!(matches!(errors[4], TypeCheckError::NamedArgumentsOnNonFunction { .. }));
+    }
+
+    #[test]
+    fn generic_functions() {
+        // Each call instantiates the generics of `id` and `pair` anew
+        let src = r#"
+            fn main(a : u8, b : u16) -> pub u16 {
+                let _x : u8 = id(a);
+                let _y = pair(a, a);
+                id(b)
+            }
+
+            fn id<T>(x : T) -> T {
+                x
+            }
+
+            fn pair<T>(x : T, y : T) -> (T, T) {
+                (x, y)
+            }
+        "#;
+        let namespace = vec![String::from("main"), String::from("id"), String::from("pair")];
+        type_check_src_code(src, namespace);
+
+        let src = r#"
+            fn main(a : u8, b : u16) {
+                let _pair = pair(a, b);
+            }
+
+            fn pair<T>(x : T, y : T) -> (T, T) {
+                (x, y)
+            }
+        "#;
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("main"), String::from("pair")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn single_error_does_not_cascade() {
+        // `x + true` is the only mistake; every expression built on top of it
+        // sees `Type::Error` and stays silent
+        let src = r#"
+            fn main(x : Field, arr : [Field; 2]) {
+                let y = x + true;
+                let z = -(y * 2) + arr[y];
+                let w = z as u8;
+                assert(w == 3);
+                assert(foo(z) == x);
+            }
+
+            fn foo(a : Field) -> Field {
+                a
+            }
+        "#;
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("main"), String::from("foo")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn default_parameter_values() {
+        let src = r#"
+            fn main(a : u8) -> pub u8 {
+                foo(a) + foo(a, 2) + foo(scale: 3, x: a)
+            }
+
+            fn foo(x : u8, scale : u8 = 1) -> u8 {
+                x * scale
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
+
+        let src = r#"
+            fn main() -> pub u8 {
+                foo()
+            }
+
+            fn foo(x : u8, scale : u8 = 1) -> u8 {
+                x * scale
+            }
+        "#;
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("main"), String::from("foo")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("but 0 were given")
+        ));
+
+        let src = r#"
+            fn main(a : u8) -> pub u8 {
+                foo(a)
+            }
+
+            fn foo(x : u8, scale : u8 = true) -> u8 {
+                x * scale
+            }
+        "#;
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("main"), String::from("foo")]);
+        assert!(errors.iter().any(|error| matches!(error, TypeCheckError::TypeMismatch { .. })));
+
+        // Every call which leaves out the default is given a separate copy of it
+        let src = r#"
+            fn main(a : u8) -> pub u8 {
+                foo(a) + foo(a)
+            }
+
+            fn foo(x : u8, scale : u8 = 3) -> u8 {
+                x * scale
+            }
+        "#;
+        let names = vec![String::from("main"), String::from("foo")];
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+
+        let defaults = vecmap(
+            interner.expressions_in(func_ids[0]).filter(|(id, _)| {
+                matches!(interner.expression(id), HirExpression::Literal(HirLiteral::Integer(_)))
+            }),
+            |(id, _)| id,
+        );
+        assert_eq!(defaults.len(), 2);
+        assert_ne!(defaults[0], defaults[1]);
+    }
+
+    #[test]
+    fn function_body_matches_return_type() {
+        let src = r#"
+            fn main(x : Field) -> pub Field {
+                x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main() -> pub u8 {
+                3
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : Field) -> pub Field {
+                x == 1
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::TypeMismatch { expected_typ, expr_typ, .. }
+                if expected_typ.ends_with("Field") && expr_typ.ends_with("bool")
+        ));
+    }
+
     #[test]
     fn basic_for_expr() {
         let src = r#"
@@ -282,53 +860,2277 @@ mod test {
         type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
     }
 
-    // This is the same Stub that is in the resolver, maybe we can pull this out into a test module and re-use?
-    struct TestPathResolver(HashMap<String, ModuleDefId>);
+    #[test]
+    fn call_indexed_function() {
+        let src = r#"
+            fn main(x : Field, i : Field) {
+                let fns = [foo, bar];
+                let _z = x + fns[i](x);
+            }
 
-    impl PathResolver for TestPathResolver {
-        fn resolve(
-            &self,
-            _def_maps: &HashMap<CrateId, CrateDefMap>,
-            path: Path,
-        ) -> Result<ModuleDefId, PathResolutionError> {
-            // Not here that foo::bar and hello::foo::bar would fetch the same thing
-            let name = path.segments.last().unwrap();
-            self.0
-                .get(&name.0.contents)
-                .cloned()
-                .ok_or_else(move || PathResolutionError::Unresolved(name.clone()))
-        }
+            fn foo(x : Field) -> Field {
+                x
+            }
 
-        fn local_module_id(&self) -> LocalModuleId {
-            LocalModuleId(arena::Index::from_raw_parts(0, 0))
-        }
+            fn bar(x : Field) -> Field {
+                x + 1
+            }
+        "#;
 
-        fn module_id(&self) -> ModuleId {
-            ModuleId { krate: CrateId::dummy_id(), local_id: self.local_module_id() }
-        }
+        let namespace = vec![String::from("main"), String::from("foo"), String::from("bar")];
+        type_check_src_code(src, namespace);
     }
 
-    impl TestPathResolver {
-        fn insert_func(&mut self, name: String, func_id: FuncId) {
-            self.0.insert(name, func_id.into());
+    #[test]
+    fn call_indexed_non_function() {
+        let src = r#"
+            fn main(x : Field) {
+                let values = [x, x];
+                let _z = values[0](x);
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            TypeCheckError::Unstructured { msg, span } => {
+                assert!(msg.starts_with("Expected a function, but found"), "{msg}");
+                assert_eq!(&src[std::ops::Range::<usize>::from(*span)], "values[0](x)");
+            }
+            other => panic!("expected an error for calling a Field, found {other:?}"),
         }
     }
 
-    // This function assumes that there is only one function and this is the
-    // func id that is returned
-    fn type_check_src_code(src: &str, func_namespace: Vec<String>) {
-        let (program, errors) = parse_program(src);
-        let mut interner = NodeInterner::default();
+    #[test]
+    fn array_cast_widening_elements() {
+        let src = r#"
+            fn main(x : [u8; 4]) {
+                let _y: [u16; 4] = x as [u16; 4];
+            }
+        "#;
 
-        // Using assert_eq here instead of assert(errors.is_empty()) displays
-        // the whole vec if the assert fails rather than just two booleans
-        assert_eq!(errors, vec![]);
+        type_check_src_code(src, vec![String::from("main")]);
+    }
 
-        let main_id = interner.push_fn(HirFunction::empty());
-        interner.push_function_definition("main".into(), main_id);
+    #[test]
+    fn nested_array_cast() {
+        let src = r#"
+            fn main(x : [[u8; 2]; 3]) {
+                let _y: [[u16; 2]; 3] = x as [[u16; 2]; 3];
+            }
+        "#;
 
-        let func_ids = vecmap(&func_namespace, |name| {
-            let id = interner.push_fn(HirFunction::empty());
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn array_cast_changing_length() {
+        let src = r#"
+            fn main(x : [u8; 4]) {
+                let _y = x as [u16; 2];
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            TypeCheckError::Unstructured { msg, span } => {
+                assert_eq!(msg, "Cannot cast an array of length 4 to an array of length 2");
+                assert_eq!(&src[std::ops::Range::<usize>::from(*span)], "x as [u16; 2]");
+            }
+            other => panic!("expected an error for the length change, found {other:?}"),
+        }
+
+        // Each element must be castable on its own
+        let src = r#"
+            fn main(x : [Field; 2]) {
+                let _y = x as [bool; 2];
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.starts_with("Cannot cast a Field to bool")
+        ));
+    }
+
+    #[test]
+    fn large_constant_defaults_to_field() {
+        let src = r#"
+            fn main(x : Field) {
+                let _y = 18446744073709551616;
+                let _z = x + 5;
+            }
+        "#;
+
+        let options = TypeCheckOptions {
+            large_constant_threshold: Some(u64::MAX as u128),
+            ..Default::default()
+        };
+        let warnings =
+            type_check_src_code_with_options(src, vec![String::from("main")], options).warnings;
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(warnings[0], TypeCheckWarning::LargeConstantPromotedToField { .. }));
+    }
+
+    #[test]
+    fn small_or_annotated_constants_are_silent() {
+        let src = r#"
+            fn main() {
+                let _x = 18446744073709551615;
+                let _y: Field = 18446744073709551616;
+            }
+        "#;
+
+        let options = TypeCheckOptions {
+            large_constant_threshold: Some(u64::MAX as u128),
+            ..Default::default()
+        };
+        let output = type_check_src_code_with_options(src, vec![String::from("main")], options);
+        assert_eq!(output.warnings, vec![]);
+    }
+
+    #[test]
+    fn assert_with_message() {
+        let src = r#"
+            fn main(x : Field, y : Field) {
+                assert(x == y, "x and y should be equal");
+                assert(x == y);
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn assert_with_non_bool_condition() {
+        let src = r#"
+            fn main() {
+                assert(5, "message");
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn assert_with_non_string_message() {
+        let src = r#"
+            fn main(x : Field) {
+                assert(x == 1, x);
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn variable_array_for_fixed_parameter() {
+        let src = r#"
+            fn foo(x : [u8]) {
+                bar(x);
+            }
+
+            fn bar(_y : [u8; 4]) {}
+        "#;
+
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("foo"), String::from("bar")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], TypeCheckError::VariableArrayForFixedParameter { .. }));
+    }
+
+    #[test]
+    fn array_size_combinations() {
+        let names = || vec![String::from("main"), String::from("fixed"), String::from("any")];
+        let src = r#"
+            fn main(x : [u8; 4], y : [u8; 2]) {
+                fixed(x);
+                any(x);
+                any(y);
+            }
+
+            fn fixed(_x : [u8; 4]) {}
+            fn any(_x : [u8]) {}
+        "#;
+        type_check_src_code(src, names());
+
+        let src = r#"
+            fn main(x : [u8; 3], y : [[Field; 4]; 1]) {
+                fixed(x);
+                any(y);
+            }
+
+            fn fixed(_x : [u8; 4]) {}
+            fn any(_x : [[u8; 4]]) {}
+        "#;
+        let errors = type_check_src_code_errors(src, names());
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            TypeCheckError::ArrayLengthMismatch { expected: 4, found: 3, .. }
+        ));
+        assert!(matches!(errors[1], TypeCheckError::Context { .. }));
+    }
+
+    #[test]
+    fn array_argument_element_mismatch() {
+        let src = r#"
+            fn main(x : [u8; 3]) {
+                takes_u16s(x);
+            }
+
+            fn takes_u16s(_x : [u16; 3]) {}
+        "#;
+        let names = vec![String::from("main"), String::from("takes_u16s")];
+        let errors = type_check_src_code_errors(src, names);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            TypeCheckError::Context { err, .. } => match &**err {
+                TypeCheckError::TypeMismatch { expected_typ, expr_typ, .. } => {
+                    assert_eq!((expected_typ.as_str(), expr_typ.as_str()), ("u16", "u8"));
+                }
+                other => panic!("expected an element type mismatch, found {other:?}"),
+            },
+            other => panic!("expected an element type mismatch, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn valid_main_signature() {
+        let src = r#"
+            fn main(x : Field, _y : pub [u8; 4], _z : str<5>) -> pub Field {
+                x
+            }
+        "#;
+
+        let (interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        assert_eq!(super::type_check_main(&interner, func_ids[0]), vec![]);
+    }
+
+    #[test]
+    fn generic_main_parameter() {
+        let src = r#"
+            fn main<T>(x : T) -> T {
+                x
+            }
+        "#;
+
+        let (interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        let errors = super::type_check_main(&interner, func_ids[0]);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], TypeCheckError::TypeCannotBeUsed { .. }));
+    }
+
+    #[test]
+    fn expressions_in_function() {
+        let src = r#"
+            fn main(x : Field) {
+                let _y = x + 1;
+            }
+        "#;
+
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+
+        // The function body block, the infix expression, and its two operands
+        let expressions: Vec<_> = interner.expressions_in(func_ids[0]).collect();
+        assert_eq!(expressions.len(), 4);
+        assert!(matches!(expressions[0].1, Type::Unit));
+        assert!(matches!(expressions[2].1, Type::FieldElement(_)));
+    }
+
+    #[test]
+    fn field_to_integer_cast_warning() {
+        let src = r#"
+            fn main(x : Field) {
+                let _y = x as u8;
+                let _z = 3 as u8;
+            }
+        "#;
+
+        let options = TypeCheckOptions { warn_field_to_integer_casts: true, ..Default::default() };
+        let warnings =
+            type_check_src_code_with_options(src, vec![String::from("main")], options).warnings;
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(warnings[0], TypeCheckWarning::FieldToIntegerCast { .. }));
+
+        type_check_src_code(src, vec![String::from("main")]);
+
+        // Only casts to integers are lossy, a cast to a bool is still an error
+        let src = r#"
+            fn main(x : Field) {
+                let _y = x as u32;
+                let _z = x as bool;
+            }
+        "#;
+        let options = TypeCheckOptions { warn_field_to_integer_casts: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, vec![String::from("main")], options);
+        assert_eq!(output.warnings.len(), 1);
+        assert!(matches!(
+            &output.warnings[0],
+            TypeCheckWarning::FieldToIntegerCast { typ, .. } if typ.to_string() == "u32"
+        ));
+        assert_eq!(output.errors.len(), 1);
+        assert!(matches!(output.errors[0], TypeCheckError::Unstructured { .. }));
+    }
+
+    #[test]
+    fn redundant_cast_warning() {
+        let src = r#"
+            fn main(x : u8) {
+                let _y = x as u8;
+                let _z = x as u16;
+            }
+        "#;
+
+        let options = TypeCheckOptions { warn_redundant_casts: true, ..Default::default() };
+        let warnings =
+            type_check_src_code_with_options(src, vec![String::from("main")], options).warnings;
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0],
+            TypeCheckWarning::RedundantCast { typ: Type::Integer(_, _, 8), .. }
+        ));
+    }
+
+    #[test]
+    fn cast_rules() {
+        let src = r#"
+            fn main(x : u32, b : bool, f : Field, bits : [u1; 2]) {
+                let _narrowed = x as u8;
+                let _widened = x as u64;
+                let _from_literal = 3 as u8;
+                let _from_bool = b as u8;
+                let _to_field = x as Field;
+                let _to_integer = f as u32;
+                let _to_bool = bits[0] as bool;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(f : Field, x : [Field; 2], t : (Field, Field)) {
+                let _a = f as bool;
+                let _b = x as Field;
+                let _c = f as [Field; 2];
+                let _d = t as Field;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::Unstructured { .. })));
+    }
+
+    #[test]
+    fn bool_casts() {
+        let src = r#"
+            fn main(b : bool) -> pub Field {
+                let _x : u8 = b as u8;
+                let _y : i32 = b as i32;
+                b as Field
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(b : bool) {
+                let _a = b as [u8; 2];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("Only integer and Field types")
+        ));
+    }
+
+    #[test]
+    fn constant_cast_overflow() {
+        let src = r#"
+            fn main() {
+                let _a = 255 as u8;
+                let _b = 127 as i8;
+                let _c = 0 as u1;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main() {
+                let _a = 300 as u8;
+                let _b = 128 as i8;
+                let _c = 2 as u1;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], TypeCheckError::CastOverflow { value: 300, .. }));
+        assert!(matches!(errors[1], TypeCheckError::CastOverflow { value: 128, .. }));
+        assert!(matches!(errors[2], TypeCheckError::CastOverflow { value: 2, .. }));
+    }
+
+    #[test]
+    fn constant_arithmetic_overflow() {
+        let src = r#"
+            fn main(x : u8) {
+                let mut a: u8 = 200 + 55;
+                a = x + 255;
+                let _b: i8 = 2 * 60 + 7;
+                let _c = (100 + 100) as u8;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main() {
+                let mut a: u8 = 200 + 100;
+                a = 16 * 16;
+                let _b = (250 + 10) as u8;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], TypeCheckError::ConstantOverflow { value: 300, .. }));
+        assert!(matches!(errors[1], TypeCheckError::ConstantOverflow { value: 256, .. }));
+        assert!(matches!(errors[2], TypeCheckError::CastOverflow { value: 260, .. }));
+    }
+
+    #[test]
+    fn integer_literal_ranges() {
+        let src = r#"
+            fn main(x : u8, y : i8) {
+                let _a : u8 = 255;
+                let _b : i8 = 127;
+                let _c : i8 = -128;
+                let _d = x + 255;
+                let _e = y - 127;
+                let _f = foo(255);
+            }
+
+            fn foo(x : u8) -> u8 {
+                x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
+
+        let src = r#"
+            fn main(x : u8) {
+                let _a : u8 = 256;
+                let _b : i8 = 128;
+                let _c : i8 = -129;
+                let _d = x + 256;
+                let _e = foo(300);
+                let _f = 300 as u8;
+                let _g : u8 = -1;
+                let _h : u8 = -0;
+            }
+
+            fn foo(x : u8) -> u8 {
+                x
+            }
+        "#;
+        let errors =
+            type_check_src_code_errors(src, vec![String::from("main"), String::from("foo")]);
+        assert_eq!(errors.len(), 7);
+        assert!(matches!(errors[0], TypeCheckError::CastOverflow { value: 300, .. }));
+        assert!(matches!(errors[1], TypeCheckError::LiteralOutOfRange { value: 256, .. }));
+        assert!(matches!(errors[2], TypeCheckError::LiteralOutOfRange { value: 128, .. }));
+        assert!(matches!(errors[3], TypeCheckError::LiteralOutOfRange { value: 129, .. }));
+        assert!(matches!(errors[4], TypeCheckError::LiteralOutOfRange { value: 256, .. }));
+        assert!(matches!(errors[5], TypeCheckError::LiteralOutOfRange { value: 300, .. }));
+        assert!(matches!(
+            errors[6],
+            TypeCheckError::LiteralOutOfRange { value: 1, negated: true, .. }
+        ));
+    }
+
+    #[test]
+    fn field_literals() {
+        let src = r#"
+            fn main(x : Field) -> pub Field {
+                let _a : [Field; 2] = [1_Field, 0x02_Field];
+                x * 5_Field + 1
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : u8) {
+                let _a : u8 = 5_Field;
+                let _b = x + 1_Field;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], TypeCheckError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn integer_literals_take_type_from_context() {
+        let src = r#"
+            fn main(x : u8) {
+                let mut y: u8 = 5;
+                y = x + 3;
+                takes_u8(7);
+                let _z = [x, 1, 2];
+            }
+
+            fn takes_u8(_x : u8) {}
+        "#;
+
+        let names = vec![String::from("main"), String::from("takes_u8")];
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        for func_id in &func_ids {
+            assert_eq!(super::type_check_func(&mut interner, *func_id).errors, vec![]);
+        }
+
+        let literal_types: Vec<_> = interner
+            .expressions_in(func_ids[0])
+            .filter(|(id, _)| {
+                matches!(interner.expression(id), HirExpression::Literal(HirLiteral::Integer(_)))
+            })
+            .map(|(_, typ)| typ.follow_bindings())
+            .collect();
+
+        assert_eq!(literal_types.len(), 5);
+        for typ in literal_types {
+            assert!(matches!(typ, Type::Integer(_, Signedness::Unsigned, 8)), "{typ} is not u8");
+        }
+    }
+
+    #[test]
+    fn query_expression_types() {
+        let src = r#"
+            fn main(x : Field) {
+                let _y = x + 1;
+                let _z = x.unknown(2);
+            }
+        "#;
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+
+        let literals: Vec<_> = interner
+            .expressions_in(func_ids[0])
+            .map(|(id, _)| id)
+            .filter(|id| {
+                matches!(interner.expression(id), HirExpression::Literal(HirLiteral::Integer(_)))
+            })
+            .collect();
+        assert_eq!(literals.len(), 2);
+        assert_eq!(interner.try_expr_type(literals[0]), None);
+
+        let errors = super::type_check_func(&mut interner, func_ids[0]).errors;
+        assert_eq!(errors.len(), 1);
+
+        // The arguments of a call to an unknown method are never checked
+        let checked = interner.try_expr_type(literals[0]).map(|typ| typ.follow_bindings());
+        assert!(matches!(checked, Some(Type::FieldElement(_))));
+        assert_eq!(interner.try_expr_type(literals[1]), None);
+    }
+
+    #[test]
+    fn errors_and_warnings_are_partitioned() {
+        let src = r#"
+            fn main(x : Field) {
+                let _y = x as u8;
+                let _z: bool = x;
+            }
+        "#;
+
+        let options = TypeCheckOptions { warn_field_to_integer_casts: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, vec![String::from("main")], options);
+        assert_eq!(output.errors.len(), 1);
+        assert!(matches!(output.errors[0], TypeCheckError::TypeMismatch { .. }));
+        assert_eq!(output.warnings.len(), 1);
+        assert!(matches!(output.warnings[0], TypeCheckWarning::FieldToIntegerCast { .. }));
+    }
+
+    #[test]
+    fn chained_comparison() {
+        let src = r#"
+            fn main(a : u32, b : u32, c : u32) {
+                let _in_range = a < b < c;
+                let _same = (a == b) == (b == c);
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], TypeCheckError::ChainedComparison { .. }));
+    }
+
+    #[test]
+    fn destructure_foreign_tuple_output() {
+        let src = r#"
+            fn main(x : Field) {
+                let (px, py) = point(x);
+                let _sum: Field = px + py;
+            }
+
+            #[foreign(fixed_base_scalar_mul)]
+            fn point(_input : Field) -> (Field, Field) {}
+        "#;
+
+        type_check_src_code(src, vec![String::from("main"), String::from("point")]);
+    }
+
+    #[test]
+    fn conditional_expressions() {
+        let src = r#"
+            fn main(c : bool, x : u8, y : u8) -> pub u8 {
+                let _max : u8 = x > y ? x : y;
+                let _f : Field = c ? 1 : 2;
+                c ? x : 1
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(c : bool, x : u8, f : Field) {
+                let _a = c ? x : true;
+                let _b = f ? x : 1;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(&errors[0], TypeCheckError::Context { .. }));
+        assert!(matches!(
+            &errors[1],
+            TypeCheckError::TypeMismatch { expected_typ, .. } if expected_typ == "bool"
+        ));
+    }
+
+    #[test]
+    fn multiple_return_values() {
+        let names = vec![String::from("main"), String::from("split")];
+        let src = r#"
+            fn main(x : Field) -> pub u8 {
+                let (high, low) = split(x);
+                let _double : Field = high * 2;
+                low
+            }
+
+            fn split(x : Field) -> (Field, u8) {
+                (x, x as u8)
+            }
+        "#;
+        type_check_src_code(src, names.clone());
+
+        let src = r#"
+            fn main(x : Field) {
+                let (_high, _low, _rest) = split(x);
+            }
+
+            fn split(x : Field) -> (Field, u8) {
+                (x, x as u8)
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, names);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::TypeMismatch { expected_typ, expr_typ, .. }
+                if expected_typ == "(_, _, _)" && expr_typ == "(Field, u8)"
+        ));
+    }
+
+    #[test]
+    fn tuple_expressions() {
+        let src = r#"
+            fn main(x : u8) -> pub bool {
+                let pair = (1, true);
+                let (_a, b) = pair;
+                let _c: u8 = first((x, b));
+                second(pair)
+            }
+
+            fn first(pair : (u8, bool)) -> u8 {
+                pair.0
+            }
+
+            fn second(pair : (Field, bool)) -> bool {
+                pair.1
+            }
+        "#;
+        let names = vecmap(["main", "first", "second"], String::from);
+        type_check_src_code(src, names.clone());
+
+        let src = r#"
+            fn main(x : u8) {
+                let _a = (1, 2) + (3, 4);
+                let _b = first((true, x));
+            }
+
+            fn first(pair : (u8, bool)) -> u8 {
+                pair.0
+            }
+
+            fn second(pair : (Field, bool)) -> bool {
+                pair.1
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, names);
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn report_unresolved_types() {
+        let src = r#"
+            fn main(x : Field) {
+                let _f = |y| y;
+                let _g = |z: Field| z + x;
+            }
+        "#;
+
+        let options = TypeCheckOptions { report_unresolved_types: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, vec![String::from("main")], options);
+        assert_eq!(output.errors, vec![]);
+
+        // The lambda `|y| y` and its body, as nothing constrains the type of `y`
+        let unresolved = output.unresolved_types();
+        assert_eq!(unresolved.len(), 2);
+        assert!(matches!(unresolved[0].1, Type::Function(..)));
+        assert!(matches!(unresolved[1].1.follow_bindings(), Type::TypeVariable(_)));
+
+        let output = type_check_src_code_with_options(
+            src,
+            vec![String::from("main")],
+            TypeCheckOptions::default(),
+        );
+        assert!(output.unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn export_typed_program() {
+        let src = r#"
+            fn main(x : u8) -> pub u8 {
+                double(x) + x.double()
+            }
+
+            fn double(y : u8) -> u8 {
+                y * 2 + true
+            }
+        "#;
+        let names = vec![String::from("main"), String::from("double")];
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+
+        // The function created for a method call must have a location to be exported
+        let u8_type = Type::Integer(CompTime::No(None), Signedness::Unsigned, 8);
+        interner.add_method(&u8_type, String::from("double"), func_ids[1]);
+
+        for func_id in func_ids {
+            super::type_check_func(&mut interner, func_id);
+        }
+
+        let export = super::export_typed_program(&interner);
+        let json = serde_json::to_string(&export).unwrap();
+        assert_eq!(serde_json::from_str::<super::ExportedProgram>(&json).unwrap(), export);
+
+        // Each expression names its type in a field called `type`
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert!(value["functions"][0]["expressions"][0]["type"].is_object());
+
+        let functions = &export.functions;
+        let names = vecmap(functions, |function| function.name.as_str());
+        assert_eq!(names, vec!["main", "double"]);
+
+        let types = |function: &super::ExportedFunction| {
+            vecmap(&function.expressions, |expression| expression.typ.clone())
+        };
+
+        // The body of main and the sum within it, and each call to double
+        let u8_type = ExportedType::Integer { signed: false, width: 8 };
+        let main_types = types(&functions[0]);
+        assert_eq!(&main_types[..3], &[u8_type.clone(), u8_type.clone(), u8_type.clone()]);
+        assert_eq!(main_types.iter().filter(|typ| **typ == u8_type).count(), 6);
+
+        // `y * 2 + true` failed to type check
+        assert!(types(&functions[1]).contains(&ExportedType::Error));
+    }
+
+    #[test]
+    fn type_at_span() {
+        let src = r#"
+            fn main(a : u8, b : u8, c : u16) -> pub u16 {
+                c + (a * b) as u16
+            }
+        "#;
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        super::type_check_func(&mut interner, func_ids[0]);
+
+        let at = |text: &str, length: u32| {
+            let start = src.find(text).unwrap() as u32;
+            Location::new(Span::exclusive(start, start + length), FileId::default())
+        };
+        let type_at = |location| super::type_at_span(&interner, location).unwrap().to_string();
+
+        // The cursor on `a` gives the identifier rather than any expression containing it
+        let a = interner.expression_at(at("a * b", 1)).unwrap();
+        assert!(matches!(interner.expression(&a), HirExpression::Ident(_)));
+        assert_eq!(type_at(at("a * b", 1)), "u8");
+
+        // Selecting `a * b` gives the multiplication within the cast
+        let product = interner.expression_at(at("a * b", 5)).unwrap();
+        assert!(matches!(interner.expression(&product), HirExpression::Infix(_)));
+        assert_eq!(type_at(at("a * b", 5)), "u8");
+
+        assert_eq!(type_at(at("as u16", 2)), "u16");
+        assert_eq!(type_at(at("c + ", 3)), "u16");
+
+        let outside = Location::new(Span::exclusive(0, 1), FileId::default());
+        assert_eq!(super::type_at_span(&interner, outside), None);
+    }
+
+    #[test]
+    fn constant_values() {
+        let src = r#"
+            fn main(x : Field) {
+                let _a = 7;
+                let _b = 2 + 3;
+                let _c = x + 1;
+            }
+        "#;
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        super::type_check_func(&mut interner, func_ids[0]);
+
+        let at = |text: &str| {
+            let start = src.find(text).unwrap() as u32;
+            let location =
+                Location::new(Span::exclusive(start, start + text.len() as u32), FileId::default());
+            interner.expression_at(location).unwrap()
+        };
+
+        assert_eq!(interner.constant_value(at("7")), Some(7));
+        assert_eq!(interner.constant_value(at("2 + 3")), Some(5));
+        assert_eq!(interner.constant_value(at("x + 1")), None);
+    }
+
+    #[test]
+    fn let_types_inferred_from_later_uses() {
+        // Neither `count` nor `f` is annotated, their types are only fixed by the
+        // arithmetic with `a` which follows
+        let src = r#"
+            fn main(a : u32) -> pub u32 {
+                let mut count = 0;
+                count = count + a;
+                let f = |y| y;
+                f(a) * count
+            }
+        "#;
+
+        let options = TypeCheckOptions { report_unresolved_types: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, vec![String::from("main")], options);
+        assert_eq!(output.errors, vec![]);
+        assert!(output.unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn loop_iteration_limit() {
+        let loop_of = |iterations: u64| {
+            format!(
+                "
+                fn main() {{
+                    for _i in 0..{iterations} {{
+                    }}
+                }}
+                "
+            )
+        };
+        let options = TypeCheckOptions { max_loop_iterations: Some(10), ..Default::default() };
+
+        for iterations in [9, 10] {
+            let src = loop_of(iterations);
+            let output =
+                type_check_src_code_with_options(&src, vec![String::from("main")], options.clone());
+            assert_eq!(output.errors, vec![]);
+        }
+
+        let src = loop_of(11);
+        let output = type_check_src_code_with_options(&src, vec![String::from("main")], options);
+        assert_eq!(output.errors.len(), 1);
+        assert!(matches!(
+            output.errors[0],
+            TypeCheckError::LoopIterationLimitExceeded { iterations: 11, limit: 10, .. }
+        ));
+    }
+
+    #[test]
+    fn dynamic_loop_bounds() {
+        let src = r#"
+            fn main(n : Field) {
+                for i in 0..n {
+                    let _x: Field = i;
+                }
+            }
+        "#;
+        let names = vec![String::from("main")];
+        let output = type_check_src_code_with_options(src, names.clone(), Default::default());
+        assert_eq!(output.errors.len(), 1);
+        assert!(matches!(output.errors[0], TypeCheckError::Context { .. }));
+
+        let options = TypeCheckOptions { allow_dynamic_loops: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, names, options);
+        assert_eq!(output.errors, vec![]);
+    }
+
+    #[test]
+    fn while_loops() {
+        let src = r#"
+            unconstrained fn main(n : u32) -> pub u32 {
+                let mut i : u32 = 0;
+                while i < n {
+                    i = i + 1;
+                }
+                i
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        // The loop type checks, but is reported as unsupported rather than lowered
+        let names = vec![String::from("main")];
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+        let errors = monomorphize(func_ids[0], &interner).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            MonomorphizationError::UnsupportedFeature { feature: "While loops", .. }
+        ));
+
+        let src = r#"
+            fn main(n : u32) {
+                let mut i : u32 = 0;
+                while i < n {
+                    i = i + 1;
+                }
+            }
+        "#;
+        let names = vec![String::from("main")];
+        let output = type_check_src_code_with_options(src, names.clone(), Default::default());
+        assert_eq!(output.errors.len(), 1);
+        assert!(matches!(
+            &output.errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("While loops")
+        ));
+
+        let options = TypeCheckOptions { allow_dynamic_loops: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, names, options);
+        assert_eq!(output.errors.len(), 1);
+
+        let src = r#"
+            unconstrained fn main(n : u32) {
+                while n {
+                }
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::TypeMismatch { expected_typ, .. } if expected_typ == "bool"
+        ));
+    }
+
+    #[test]
+    fn early_returns() {
+        let src = r#"
+            unconstrained fn main(x : u8) -> pub u8 {
+                if x > 3 {
+                    return 3;
+                }
+                return x;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            unconstrained fn main(x : u8) {
+                if x == 0 {
+                    return;
+                }
+                let _y = x;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            unconstrained fn main(c : bool) -> pub u8 {
+                if c {
+                    return;
+                }
+                return true;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::TypeMismatch { expected_typ, expr_typ, .. }
+                if expected_typ == "u8" && expr_typ == "()"
+        ));
+        assert!(matches!(
+            &errors[1],
+            TypeCheckError::TypeMismatch { expected_typ, expr_typ, .. }
+                if expected_typ == "u8" && expr_typ == "bool"
+        ));
+
+        let src = r#"
+            fn main(x : u8) -> pub u8 {
+                return x;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("Early returns")
+        ));
+
+        // A block ending in a return has no value, so it does not need to match the other branch
+        let src = r#"
+            unconstrained fn main(c : bool) -> pub u8 {
+                if c {
+                    return 1;
+                } else {
+                    return 2;
+                }
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        // Returns type check, but are reported as unsupported rather than lowered
+        let names = vec![String::from("main")];
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+        let errors = monomorphize(func_ids[0], &interner).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        for error in errors {
+            assert!(matches!(
+                error,
+                MonomorphizationError::UnsupportedFeature { feature: "Early returns", .. }
+            ));
+        }
+
+        let src = r#"
+            unconstrained fn main(c : bool) -> pub u8 {
+                let x = if c { 1 } else { return 2; };
+                x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            unconstrained fn main(x : u8) -> pub u8 {
+                let f = |y : u8| {
+                    return y;
+                };
+                f(x)
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("lambda")
+        ));
+    }
+
+    #[test]
+    fn u1_as_bool() {
+        let names = vec![String::from("main"), String::from("foo"), String::from("bar")];
+        let src = r#"
+            fn main(x : u1, y : u1, b : bool) -> pub u1 {
+                assert(foo(x));
+                assert(x == b);
+                let _c : bool = x & b;
+                let _d : bool = b | y;
+                let _e : u1 = bar(b);
+                x + y
+            }
+
+            fn foo(a : bool) -> bool {
+                a
+            }
+
+            fn bar(a : u1) -> u1 {
+                a
+            }
+        "#;
+        type_check_src_code(src, names.clone());
+
+        // Each u1 used as a bool, and each bool used as a u1, is converted explicitly
+        let options = TypeCheckOptions::default();
+        let (mut interner, func_ids) = resolve_src_code(src, names.clone(), options);
+        for func_id in &func_ids {
+            assert_eq!(super::type_check_func(&mut interner, *func_id).errors, vec![]);
+        }
+        let program = monomorphize(func_ids[0], &interner).unwrap().to_string();
+        assert_eq!(program.matches(" as bool)").count(), 4, "{program}");
+        assert_eq!(program.matches(" as u1)").count(), 1, "{program}");
+
+        let src = r#"
+            fn main(x : u1, b : bool, z : u2) {
+                let _a = x + b;
+                let _b = foo(z);
+            }
+
+            fn foo(a : bool) -> bool {
+                a
+            }
+
+            fn bar(a : u1) -> u1 {
+                a
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, names);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], TypeCheckError::Unstructured { .. }));
+        assert!(matches!(errors[1], TypeCheckError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn explicit_int_coercion() {
+        let names = vec![String::from("main")];
+        let src = r#"
+            fn main(x : u8) -> pub u8 {
+                x + 3
+            }
+        "#;
+        type_check_src_code(src, names.clone());
+
+        let options =
+            TypeCheckOptions { require_explicit_int_coercion: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, names.clone(), options.clone());
+        assert_eq!(output.errors.len(), 1);
+        assert!(matches!(
+            &output.errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("`3 as u8`")
+        ));
+
+        let src = r#"
+            fn main(x : u8, f : Field) -> pub u8 {
+                let _g = f + 1;
+                x + 3 as u8
+            }
+        "#;
+        let output = type_check_src_code_with_options(src, names, options);
+        assert_eq!(output.errors, vec![]);
+    }
+
+    #[test]
+    fn signedness_mix() {
+        let src = r#"
+            fn main(x : u8, y : i8) -> pub i8 {
+                assert(x != y);
+                x + y
+            }
+        "#;
+        let names = vec![String::from("main")];
+        let output = type_check_src_code_with_options(src, names.clone(), Default::default());
+        assert_eq!(output.errors.len(), 2);
+        assert_eq!(output.warnings, vec![]);
+
+        let options = TypeCheckOptions { allow_signedness_mix: true, ..Default::default() };
+        let output = type_check_src_code_with_options(src, names.clone(), options.clone());
+        assert_eq!(output.errors, vec![]);
+        assert_eq!(output.warnings.len(), 2);
+        assert!(output
+            .warnings
+            .iter()
+            .all(|warning| matches!(warning, TypeCheckWarning::SignednessMix { .. })));
+
+        // Integers of different bit sizes never share a common type
+        let src = r#"
+            fn main(x : u8, y : i16) {
+                let _z = x + y;
+            }
+        "#;
+        let output = type_check_src_code_with_options(src, names, options);
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.warnings, vec![]);
+    }
+
+    #[test]
+    fn witness_integer_mix() {
+        let src = r#"
+            fn main(x : u8, y : i64, f : Field) {
+                let _a = x + f;
+                assert(f != y);
+            }
+        "#;
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        let errors = super::type_check_func(&mut interner, func_ids[0]).errors;
+        assert_eq!(errors.len(), 2);
+
+        // Each error points at the Field operand, which should be cast to the other operand's type
+        let targets = vecmap(&errors, |error| match error {
+            TypeCheckError::WitnessIntegerMix { witness, integer_type, .. } => {
+                let name = match interner.expression(witness) {
+                    HirExpression::Ident(ident) => interner.definition_name(ident.id).to_owned(),
+                    other => panic!("Expected the Field operand, found {other:?}"),
+                };
+                (name, integer_type.to_string())
+            }
+            other => panic!("Expected a WitnessIntegerMix error, found {other:?}"),
+        });
+        assert_eq!(
+            targets,
+            vec![(String::from("f"), String::from("u8")), (String::from("f"), String::from("i64")),]
+        );
+    }
+
+    #[test]
+    fn constant_if_condition() {
+        let src = r#"
+            fn main(a : u8, b : u8) {
+                let _x = if true { a } else { b };
+                if false {
+                    let _y = a;
+                }
+            }
+        "#;
+
+        let options = TypeCheckOptions { warn_dead_branches: true, ..Default::default() };
+        let (mut interner, func_ids) = resolve_src_code(src, vec![String::from("main")], options);
+        let output = super::type_check_func(&mut interner, func_ids[0]);
+        assert_eq!(output.errors, vec![]);
+        assert_eq!(output.warnings.len(), 2);
+        assert!(matches!(output.warnings[0], TypeCheckWarning::DeadBranch { condition: true, .. }));
+        assert!(matches!(
+            output.warnings[1],
+            TypeCheckWarning::DeadBranch { condition: false, .. }
+        ));
+
+        let (_, if_type) = interner
+            .expressions_in(func_ids[0])
+            .find(|(id, _)| matches!(interner.expression(id), HirExpression::If(_)))
+            .unwrap();
+        assert!(matches!(if_type.follow_bindings(), Type::Integer(..)));
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn integer_arithmetic_requires_range_check() {
+        let src = r#"
+            fn main(x : u32, y : u32, a : Field) {
+                let _sum = x + y;
+                let _is_less = x < y;
+                let _field_sum = a + a;
+            }
+        "#;
+
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+
+        let infix_expressions: Vec<_> = interner
+            .expressions_in(func_ids[0])
+            .filter(|(id, _)| matches!(interner.expression(id), HirExpression::Infix(_)))
+            .map(|(id, _)| interner.requires_range_check(id))
+            .collect();
+        assert_eq!(infix_expressions, vec![true, false, false]);
+    }
+
+    #[test]
+    fn arithmetic_on_parameters_is_not_comptime() {
+        let src = r#"
+            fn main(x : Field, y : comptime Field) {
+                let _witness = x + 1;
+                let _known = y + 1;
+            }
+        "#;
+
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+
+        let infix_types: Vec<_> = interner
+            .expressions_in(func_ids[0])
+            .filter(|(id, _)| matches!(interner.expression(id), HirExpression::Infix(_)))
+            .map(|(_, typ)| match typ.follow_bindings() {
+                Type::FieldElement(comptime) => comptime.is_comp_time(),
+                other => panic!("Expected a Field, found {other}"),
+            })
+            .collect();
+        assert_eq!(infix_types, vec![false, true]);
+    }
+
+    #[test]
+    fn call_graph_from_main() {
+        let src = r#"
+            fn main(x : Field) {
+                let _y = a(x);
+            }
+
+            fn a(x : Field) -> Field {
+                b(x) + 1
+            }
+
+            fn b(x : Field) -> Field {
+                x
+            }
+
+            fn unused(x : Field) -> Field {
+                a(x)
+            }
+        "#;
+
+        let names = vecmap(["main", "a", "b", "unused"], String::from);
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        for func_id in &func_ids {
+            assert_eq!(super::type_check_func(&mut interner, *func_id).errors, vec![]);
+        }
+
+        let expected: HashSet<_> = func_ids[..3].iter().copied().collect();
+        assert_eq!(interner.call_graph(func_ids[0]), expected);
+    }
+
+    #[test]
+    fn indexed_assignment() {
+        let src = r#"
+            fn main(x : [u8; 3], y : u8, i : Field) {
+                let mut z = x;
+                z[2] = y;
+                z[i] = 1;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : [u8; 3], y : Field) {
+                let mut z = x;
+                z[0] = y;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], TypeCheckError::Unstructured { .. }));
+
+        let src = r#"
+            fn main(x : [u8; 3], y : u8) {
+                let mut z = x;
+                z[3] = y;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], TypeCheckError::IndexOutOfBounds { index: 3, length: 3, .. }));
+    }
+
+    #[test]
+    fn index_expressions() {
+        let src = r#"
+            fn main(x : [u8; 3], i : Field) -> pub u8 {
+                let mut sum = x[0] + x[i];
+                for j in 0..3 {
+                    sum = sum + x[j];
+                }
+                sum
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : [u8; 3], b : bool, i : i32) {
+                let _a = x[b];
+                let _b = x[i];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn nested_index_expressions() {
+        // Each index peels off one layer of the array type
+        let src = r#"
+            fn main(m : [[u8; 3]; 2], c : [[[Field; 2]; 2]; 2], i : Field) -> pub u8 {
+                let _row : [u8; 3] = m[i];
+                let _x : Field = c[1][i][0];
+                m[1][2] + m[i][i]
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : [u8; 3], m : [[u8; 3]; 2]) {
+                let _a = x[0][1];
+                let _b = m[0][3];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(
+            matches!(&errors[0], TypeCheckError::TypeMismatch { expected_typ, .. } if expected_typ == "Array")
+        );
+        assert!(matches!(errors[1], TypeCheckError::IndexOutOfBounds { index: 3, length: 3, .. }));
+    }
+
+    #[test]
+    fn repeated_arrays() {
+        let src = r#"
+            fn main(x : u8) {
+                let _a = [x; 16];
+                let _b : [u8; 16] = [0; 16];
+            }
+        "#;
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        let output = super::type_check_func(&mut interner, func_ids[0]);
+        assert!(output.errors.is_empty());
+
+        let start = src.find("[x; 16]").unwrap() as u32;
+        let location = Location::new(Span::exclusive(start, start + 7), FileId::default());
+        let typ = super::type_at_span(&interner, location).unwrap();
+        assert_eq!(typ.to_string(), "[u8; 16]");
+    }
+
+    #[test]
+    fn repeated_array_lengths_are_folded() {
+        let src = r#"
+            fn main(x : Field) {
+                let _a : [Field; 8] = [x; 2 * 4];
+                let _b : [Field; 3] = [x; (10 - 1) / 3];
+                let _c = [x; 2 * 4][7];
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : Field) {
+                let _a : [Field; 6] = [x; 2 * 4];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn array_slices() {
+        let src = r#"
+            fn main(x : [u8; 4]) -> pub u8 {
+                let y : [u8; 2] = x[1..3];
+                y[0] + x[0..4][3]
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        // Slices of a length only known at runtime are not supported yet, so runtime
+        // bounds are rejected on purpose, even in unconstrained functions
+        let src = r#"
+            unconstrained fn main(x : [u8; 4], i : u32, j : Field) {
+                let _y = x[i..j];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            TypeCheckError::Unstructured { msg, span } => {
+                assert!(msg.contains("runtime bounds is currently unsupported"), "{msg}");
+                assert_eq!(&src[std::ops::Range::<usize>::from(*span)], "x[i..j]");
+            }
+            other => panic!("expected an unsupported slice, found {other:?}"),
+        }
+
+        let src = r#"
+            fn main(x : [u8; 4], i : Field, f : Field) {
+                let _a = x[i..2];
+                let _b = x[2..5];
+                let _c = x[3..1];
+                let _d = f[0..1];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 4);
+        assert!(
+            matches!(&errors[0], TypeCheckError::Unstructured { msg, .. } if msg.contains("runtime bounds"))
+        );
+        assert!(matches!(errors[1], TypeCheckError::IndexOutOfBounds { index: 5, length: 4, .. }));
+        assert!(matches!(errors[2], TypeCheckError::Unstructured { .. }));
+        assert!(matches!(errors[3], TypeCheckError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn constant_index_out_of_bounds() {
+        let src = r#"
+            fn main(x : [u8; 3]) -> pub u8 {
+                x[3]
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], TypeCheckError::IndexOutOfBounds { index: 3, length: 3, .. }));
+
+        // Runtime indices and arrays without a known length are not checked
+        let src = r#"
+            fn main(x : [u8; 3], i : Field) -> pub u8 {
+                first(x) + x[i + 3]
+            }
+
+            fn first(x : [u8]) -> u8 {
+                x[5]
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main"), String::from("first")]);
+    }
+
+    #[test]
+    fn bit_decomposition_length() {
+        let src = r#"
+            fn main(x : Field) {
+                let _bits = to_le_bits(x, 8);
+            }
+
+            #[builtin(to_le_bits)]
+            fn to_le_bits(_x : Field, _bit_size: u32) -> [u1] {}
+        "#;
+
+        let names = vecmap(["main", "to_le_bits"], String::from);
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+
+        let (_, bits_type) = interner
+            .expressions_in(func_ids[0])
+            .find(|(id, _)| matches!(interner.expression(id), HirExpression::Call(_)))
+            .unwrap();
+        assert_eq!(bits_type.evaluate_to_u64(), Some(8));
+
+        let src = r#"
+            fn main(x : Field, n : u32) {
+                let _bits = to_le_bits(x, n);
+            }
+
+            #[builtin(to_le_bits)]
+            fn to_le_bits(_x : Field, _bit_size: u32) -> [u1] {}
+        "#;
+
+        let names = vecmap(["main", "to_le_bits"], String::from);
+        let errors = type_check_src_code_errors(src, names);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn array_length_arithmetic() {
+        let src = r#"
+            fn main(x : [u8; 4]) {
+                let _y: [u8; 5] = push_zero(x);
+                let _z: [u8; 3] = pop(x);
+            }
+
+            fn push_zero<N>(x : [u8; N]) -> [u8; N + 1] {
+                [x[0]; N + 1]
+            }
+
+            fn pop<N>(x : [u8; N + 1]) -> [u8; N] {
+                [x[0]; N]
+            }
+        "#;
+
+        let namespace = vec![String::from("main"), String::from("push_zero"), String::from("pop")];
+        type_check_src_code(src, namespace);
+    }
+
+    #[test]
+    fn array_length_arithmetic_mismatch() {
+        let src = r#"
+            fn main(x : [u8; 4]) {
+                let _y: [u8; 4] = push_zero(x);
+            }
+
+            fn push_zero<N>(x : [u8; N]) -> [u8; N + 1] {
+                [x[0]; N + 1]
+            }
+        "#;
+
+        let namespace = vec![String::from("main"), String::from("push_zero")];
+        let errors = type_check_src_code_errors(src, namespace);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn concatenated_array_lengths() {
+        let concat = r#"
+            fn concat<T, N, M>(a : [T; N], b : [T; M]) -> [T; N + M] {
+                [a[0]; N + M]
+            }
+        "#;
+        let call_type = |main: &str| {
+            let src = format!("{main}\n{concat}");
+            let names = vec![String::from("main"), String::from("concat")];
+            let (mut interner, func_ids) =
+                resolve_src_code(&src, names, TypeCheckOptions::default());
+            let errors = super::type_check_func(&mut interner, func_ids[0]).errors;
+
+            let call_type = interner
+                .expressions_in(func_ids[0])
+                .find(|(id, _)| matches!(interner.expression(id), HirExpression::Call(_)))
+                .map(|(_, typ)| typ.follow_bindings())
+                .unwrap();
+            (errors, call_type)
+        };
+
+        // Two fixed lengths fold into a fixed length
+        let (errors, typ) =
+            call_type("fn main(x : [u8; 2], y : [u8; 3]) { let _z = concat(x, y); }");
+        assert_eq!(errors, vec![]);
+        assert_eq!(typ.evaluate_to_u64(), Some(5));
+
+        // A variable length makes the result variable as well
+        let (errors, typ) = call_type("fn main(x : [u8; 2], y : [u8]) { let _z = concat(x, y); }");
+        assert_eq!(errors, vec![]);
+        assert!(matches!(&typ, Type::Array(length, _) if matches!(**length, Type::InfixExpr(..))));
+
+        let (errors, _) =
+            call_type("fn main(x : [u8; 2], y : [Field; 3]) { let _z = concat(x, y); }");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn invalid_generic_array_lengths() {
+        let src = r#"
+            fn main(x : [u8; 0]) {
+                let _a = pop(x);
+                let _b = split(x);
+            }
+
+            fn pop<N>(x : [u8; N]) -> [u8; N - 1] {
+                [x[0]; N - 1]
+            }
+
+            fn split<N>(x : [u8; N]) -> [u8; 4 / N] {
+                [x[0]; 4 / N]
+            }
+        "#;
+
+        let namespace = vec![String::from("main"), String::from("pop"), String::from("split")];
+        let errors = type_check_src_code_errors(src, namespace);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            TypeCheckError::InvalidArrayLength {
+                lhs: 0,
+                op: BinaryTypeOperator::Subtraction,
+                rhs: 1,
+                ..
+            }
+        ));
+        assert!(matches!(
+            errors[1],
+            TypeCheckError::InvalidArrayLength {
+                lhs: 4,
+                op: BinaryTypeOperator::Division,
+                rhs: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn types_compatible_without_binding() {
+        let span = Span::default();
+        let u8_type = Type::Integer(CompTime::No(None), Signedness::Unsigned, 8);
+        let array = |length: Type| Type::Array(Box::new(length), Box::new(u8_type.clone()));
+
+        // Exact matches, and comptime values passed to non-comptime parameters
+        assert!(types_compatible(&u8_type, &u8_type, span).is_ok());
+        assert!(types_compatible(&Type::comp_time(None), &Type::field(None), span).is_ok());
+        let error = types_compatible(&Type::field(None), &Type::comp_time(None), span);
+        assert!(matches!(error, Err(TypeCheckError::TypeMismatch { .. })));
+
+        // Array lengths must be the same constant
+        let fixed = array(Type::Constant(3));
+        assert!(types_compatible(&fixed, &fixed, span).is_ok());
+        let error = types_compatible(&fixed, &array(Type::Constant(4)), span);
+        assert!(matches!(
+            error,
+            Err(TypeCheckError::ArrayLengthMismatch { expected: 4, found: 3, .. })
+        ));
+
+        // A generic length cannot be passed where a constant length is required
+        let generic = Shared::new(TypeBinding::Unbound(TypeVariableId(0)));
+        let variable = array(Type::NamedGeneric(generic, Rc::new("N".into())));
+        let error = types_compatible(&variable, &fixed, span);
+        assert!(matches!(error, Err(TypeCheckError::VariableArrayForFixedParameter { .. })));
+
+        // Unbound type variables are assumed compatible and are left unbound
+        let unbound = Shared::new(TypeBinding::Unbound(TypeVariableId(1)));
+        let length = Type::TypeVariable(unbound.clone());
+        assert!(types_compatible(&fixed, &array(length), span).is_ok());
+        assert!(unbound.borrow().is_unbound());
+
+        // A type variable used twice must still be bound to a single type
+        let shared = Type::TypeVariable(unbound.clone());
+        let pair = Type::Tuple(vec![shared.clone(), shared]);
+        let same = Type::Tuple(vec![u8_type.clone(), u8_type.clone()]);
+        let mixed = Type::Tuple(vec![u8_type.clone(), Type::Bool(CompTime::No(None))]);
+        assert!(types_compatible(&same, &pair, span).is_ok());
+        assert!(types_compatible(&mixed, &pair, span).is_err());
+        assert!(unbound.borrow().is_unbound());
+    }
+
+    #[test]
+    fn bool_literals() {
+        let src = r#"
+            fn main(x : bool) {
+                let _y = if false { x } else { x == true };
+                let _z = flip(true);
+            }
+
+            fn flip(b : bool) -> bool {
+                !b
+            }
+        "#;
+
+        let names = vec![String::from("main"), String::from("flip")];
+        let (mut interner, func_ids) = resolve_src_code(src, names, TypeCheckOptions::default());
+        assert_eq!(super::type_check_func(&mut interner, func_ids[0]).errors, vec![]);
+
+        let literal_types: Vec<_> = interner
+            .expressions_in(func_ids[0])
+            .filter(|(id, _)| matches!(interner.expression(id), HirExpression::Literal(_)))
+            .map(|(_, typ)| typ.follow_bindings())
+            .collect();
+        assert_eq!(literal_types.len(), 3);
+        assert!(literal_types.iter().all(|typ| matches!(typ, Type::Bool(_))));
+
+        let src = r#"
+            fn main() {
+                let _z = flip(1);
+            }
+
+            fn flip(b : bool) -> bool {
+                !b
+            }
+        "#;
+        let names = vec![String::from("main"), String::from("flip")];
+        assert_eq!(type_check_src_code_errors(src, names).len(), 1);
+    }
+
+    #[test]
+    fn string_literals() {
+        let src = r#"
+            fn main() {
+                let message = "hello";
+                greet(message);
+            }
+
+            fn greet(_message : str<5>) {}
+        "#;
+        type_check_src_code(src, vec![String::from("main"), String::from("greet")]);
+
+        let src = r#"
+            fn main() {
+                greet("goodbye");
+            }
+
+            fn greet(_message : str<5>) {}
+        "#;
+        let names = vec![String::from("main"), String::from("greet")];
+        assert_eq!(type_check_src_code_errors(src, names).len(), 1);
+
+        let src = r#"
+            fn main() {
+                let _message = "hello" + "world";
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], TypeCheckError::Unstructured { msg, .. } if msg.contains("Strings"))
+        );
+    }
+
+    #[test]
+    fn string_indexing() {
+        let src = r#"
+            fn main() {
+                let message = "hello";
+                let first = message[0];
+                assert(first != message[1]);
+                assert(message[2] == message[3]);
+                assert(first < message[4]);
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let (mut interner, func_ids) =
+            resolve_src_code(src, vec![String::from("main")], TypeCheckOptions::default());
+        super::type_check_func(&mut interner, func_ids[0]);
+        let chars = interner
+            .expressions_in(func_ids[0])
+            .filter(|(_, typ)| matches!(typ.follow_bindings(), Type::Char))
+            .count();
+        assert_eq!(chars, 7);
+
+        let src = r#"
+            fn main() {
+                let message = "hello";
+                let _x = message[0] + message[1];
+                let _y = message[5];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(
+            matches!(&errors[0], TypeCheckError::Unstructured { msg, .. } if msg.contains("Characters"))
+        );
+        assert!(matches!(errors[1], TypeCheckError::IndexOutOfBounds { index: 5, length: 5, .. }));
+    }
+
+    #[test]
+    fn prefix_expressions() {
+        let src = r#"
+            fn main(x : Field, y : i32, z : bool) {
+                let _a = -x;
+                let _b = -y;
+                let _c = !z;
+                let _d = -5;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : u32) {
+                let _a = -x;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], TypeCheckError::Unstructured { msg, .. } if msg.contains("unsigned"))
+        );
+
+        let src = r#"
+            fn main(x : bool, y : Field) {
+                let _a = -x;
+                let _b = !y;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn assert_condition_must_be_bool() {
+        let src = r#"
+            fn main(x : Field, y : Field) {
+                assert(x == y);
+                assert((x != y) & (x == 1));
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        for typ in ["Field", "u32"] {
+            let src = format!(
+                "
+                fn main(x : {typ}) {{
+                    assert(x);
+                }}
+            "
+            );
+            let errors = type_check_src_code_errors(&src, vec![String::from("main")]);
+            assert_eq!(errors.len(), 1);
+            match &errors[0] {
+                TypeCheckError::Context { err, ctx } => {
+                    assert!(matches!(**err, TypeCheckError::TypeMismatch { .. }));
+                    assert!(ctx.contains("comparison"));
+                }
+                other => panic!("expected a type mismatch with a hint, found {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn reports_every_error() {
+        let src = r#"
+            fn main(x : Field, b : bool) {
+                let _a = [x, b, b];
+                let _b = x + b;
+                assert(x);
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 4);
+
+        let non_homogeneous = errors.iter().filter(|error| {
+            matches!(error, TypeCheckError::Context { err, .. }
+                if matches!(**err, TypeCheckError::NonHomogeneousArray { .. }))
+        });
+        assert_eq!(non_homogeneous.count(), 2);
+    }
+
+    #[test]
+    fn non_homogeneous_array_index() {
+        let src = r#"
+            fn main() {
+                let _a = [1, 2, true, 4];
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            TypeCheckError::Context { err, .. } => match **err {
+                TypeCheckError::NonHomogeneousArray {
+                    first_index,
+                    second_index,
+                    second_span,
+                    ..
+                } => {
+                    assert_eq!((first_index, second_index), (0, 2));
+                    assert_eq!(&src[std::ops::Range::<usize>::from(second_span)], "true");
+                }
+                ref other => panic!("expected a non homogeneous array, found {other:?}"),
+            },
+            other => panic!("expected a non homogeneous array, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn bitwise_operators() {
+        let src = r#"
+            fn main(a : u32, b : u32, x : bool, y : bool) -> pub u32 {
+                let _c = (x & y) | (x ^ y);
+                (a & b) | (a ^ b)
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(a : u32, b : u8, c : i32, f : Field, x : bool) {
+                let _a = a & b;
+                let _b = a | c;
+                let _c = f ^ f;
+                let _d = x + x;
+                let _e = x << x;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 5);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::Unstructured { .. })));
+    }
+
+    #[test]
+    fn assignment_types() {
+        let src = r#"
+            fn main(y : u32) -> pub u32 {
+                let mut x : u32 = 1;
+                x = y;
+                x = 5;
+                x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(f : Field) {
+                let mut x : u32 = 1;
+                x = f;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::TypeMismatch { expected_typ, expr_typ, .. }
+                if expected_typ.ends_with("u32") && expr_typ.ends_with("Field")
+        ));
+    }
+
+    #[test]
+    fn compound_assignment() {
+        // `x += y` is desugared to `x = x + y` by the parser, so it is checked
+        // as an infix expression assigned back to the same lvalue.
+        let src = r#"
+            fn main(y : u32) -> pub u32 {
+                let mut x : u32 = 2;
+                x += 1;
+                x *= y;
+                x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : u32) {
+                let mut arr = [x, x];
+                arr += 1;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert!(!errors.is_empty());
+
+        let src = r#"
+            fn main(y : u32) {
+                let x : u32 = 2;
+                x += y;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            TypeCheckError::Unstructured { msg, .. } if msg.contains("must be mutable")
+        ));
+    }
+
+    #[test]
+    fn shift_by_runtime_value() {
+        let src = r#"
+            fn main(x : u64, y : u64) {
+                let _z = x << y;
+            }
+        "#;
+
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            TypeCheckError::Unstructured { msg, span } => {
+                assert_eq!(&src[std::ops::Range::<usize>::from(*span)], "x << y");
+                assert!(msg.contains("only possible in unconstrained functions"), "{msg}");
+            }
+            other => panic!("expected an error for the runtime shift, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn shift_by_runtime_value_unconstrained() {
+        let src = r#"
+            unconstrained fn main(x : u64, y : u64) -> pub u64 {
+                let z: u64 = x << y;
+                z >> y
+            }
+        "#;
+
+        // Neither shift is reported, and both keep the type of the shifted value
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn shift_by_comptime_value() {
+        let src = r#"
+            fn main(x : u64) {
+                let _z = x >> 3;
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn shift_operands() {
+        let src = r#"
+            unconstrained fn main(x : u32, y : u8) -> pub u32 {
+                let z: u32 = x >> y;
+                (z << 2) + x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            unconstrained fn main(x : i32, y : u32, z : i8, f : Field) {
+                let _a = x << 2;
+                let _b = y << z;
+                let _c = y << f;
+                let _d = f << f;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 4);
+    }
+
+    #[test]
+    fn field_arithmetic() {
+        let src = r#"
+            fn main(f : Field, g : Field) -> pub Field {
+                let a : Field = f + 3;
+                let b : Field = 3 * f - g;
+                a + b
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        // A Field is never widened to the type of a non-numeric operand
+        let src = r#"
+            fn main(f : Field, x : [Field; 2]) {
+                let _a = f + true;
+                let _b = f * x;
+                let _c = false - f;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::Unstructured { .. })));
+    }
+
+    #[test]
+    fn public_parameters_keep_their_types() {
+        // `pub` only affects the ABI, so public operands follow the rules of their type
+        let src = r#"
+            fn main(x : pub Field, y : pub u32, z : u32, f : Field) -> pub Field {
+                let _a : u32 = y + z;
+                let _b : u32 = y * y;
+                x + f + x
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : pub Field, y : pub u32) {
+                let _a = x + y;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], TypeCheckError::WitnessIntegerMix { .. }));
+    }
+
+    #[test]
+    fn division_and_modulo() {
+        let src = r#"
+            fn main(x : u8, y : u8, f : Field) -> pub u8 {
+                let _g = f / 3;
+                x % y
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : u8, f : Field) {
+                let _a = x % 0;
+                let _b = x / 0;
+                let _c = f / 0;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::DivisionByZero { .. })));
+
+        let src = r#"
+            fn main(f : Field) {
+                let _a = f % 2;
+                let _b = 7 % 3;
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::Unstructured { .. })));
+    }
+
+    #[test]
+    fn comparison_operands() {
+        let src = r#"
+            fn main(x : [Field; 2], y : u8) {
+                let _a = true < false;
+                let _b = x == [1, 2];
+                let _c = "abc" != "def";
+                let _d = y <= 3;
+            }
+        "#;
+        type_check_src_code(src, vec![String::from("main")]);
+
+        let src = r#"
+            fn main(x : [Field; 2], f : Field) {
+                let _a = x < 3;
+                let _b = f < x;
+                let _c = x < x;
+                let _d = "abc" < "def";
+            }
+        "#;
+        let errors = type_check_src_code_errors(src, vec![String::from("main")]);
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().all(|error| matches!(error, TypeCheckError::Unstructured { .. })));
+    }
+
+    // This is the same Stub that is in the resolver, maybe we can pull this out into a test module and re-use?
+    struct TestPathResolver(HashMap<String, ModuleDefId>);
+
+    impl PathResolver for TestPathResolver {
+        fn resolve(
+            &self,
+            _def_maps: &HashMap<CrateId, CrateDefMap>,
+            path: Path,
+        ) -> Result<ModuleDefId, PathResolutionError> {
+            // Not here that foo::bar and hello::foo::bar would fetch the same thing
+            let name = path.segments.last().unwrap();
+            self.0
+                .get(&name.0.contents)
+                .cloned()
+                .ok_or_else(move || PathResolutionError::Unresolved(name.clone()))
+        }
+
+        fn local_module_id(&self) -> LocalModuleId {
+            LocalModuleId(arena::Index::from_raw_parts(0, 0))
+        }
+
+        fn module_id(&self) -> ModuleId {
+            ModuleId { krate: CrateId::dummy_id(), local_id: self.local_module_id() }
+        }
+    }
+
+    impl TestPathResolver {
+        fn insert_func(&mut self, name: String, func_id: FuncId) {
+            self.0.insert(name, func_id.into());
+        }
+    }
+
+    // This function assumes that there is only one function and this is the
+    // func id that is returned
+    fn type_check_src_code(src: &str, func_namespace: Vec<String>) {
+        let output =
+            type_check_src_code_with_options(src, func_namespace, TypeCheckOptions::default());
+        assert_eq!(output.errors, vec![]);
+        assert_eq!(output.warnings, vec![]);
+    }
+
+    // Type checks the first function in func_namespace and returns any type errors found.
+    // The names in func_namespace are expected to be in the same order as the functions in src.
+    fn type_check_src_code_errors(src: &str, func_namespace: Vec<String>) -> Vec<TypeCheckError> {
+        type_check_src_code_with_options(src, func_namespace, TypeCheckOptions::default()).errors
+    }
+
+    fn type_check_src_code_with_options(
+        src: &str,
+        func_namespace: Vec<String>,
+        options: TypeCheckOptions,
+    ) -> TypeCheckOutput {
+        let (mut interner, func_ids) = resolve_src_code(src, func_namespace, options);
+
+        // Type check section
+        super::type_check_func(&mut interner, func_ids.first().cloned().unwrap())
+    }
+
+    // Parses and resolves each function in src, returning the ids of the functions
+    // in the same order as func_namespace.
+    fn resolve_src_code(
+        src: &str,
+        func_namespace: Vec<String>,
+        options: TypeCheckOptions,
+    ) -> (NodeInterner, Vec<FuncId>) {
+        let (program, errors) = parse_program(src);
+        let mut interner = NodeInterner::default();
+        interner.set_type_check_options(options);
+
+        // Using assert_eq here instead of assert(errors.is_empty()) displays
+        // the whole vec if the assert fails rather than just two booleans
+        assert_eq!(errors, vec![]);
+
+        let func_ids = vecmap(&func_namespace, |name| {
+            let id = interner.push_fn(HirFunction::empty());
             interner.push_function_definition(name.into(), id);
             id
         });
@@ -354,20 +3156,14 @@ mod test {
             },
         );
 
-        let func_meta = vecmap(program.functions, |nf| {
+        for (nf, func_id) in program.functions.into_iter().zip(func_ids.clone()) {
             let resolver = Resolver::new(&mut interner, &path_resolver, &def_maps, file);
-            let (hir_func, func_meta, resolver_errors) = resolver.resolve_function(nf, main_id);
+            let (hir_func, func_meta, resolver_errors) = resolver.resolve_function(nf, func_id);
             assert_eq!(resolver_errors, vec![]);
-            (hir_func, func_meta)
-        });
-
-        for ((hir_func, meta), func_id) in func_meta.into_iter().zip(func_ids.clone()) {
             interner.update_fn(func_id, hir_func);
-            interner.push_fn_meta(meta, func_id);
+            interner.push_fn_meta(func_meta, func_id);
         }
 
-        // Type check section
-        let errors = super::type_check_func(&mut interner, func_ids.first().cloned().unwrap());
-        assert_eq!(errors, vec![]);
+        (interner, func_ids)
     }
 }
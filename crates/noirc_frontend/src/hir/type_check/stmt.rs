@@ -47,6 +47,7 @@ impl<'interner> TypeChecker<'interner> {
             HirStatement::Let(let_stmt) => self.check_let_stmt(let_stmt),
             HirStatement::Constrain(constrain_stmt) => self.check_constrain_stmt(constrain_stmt),
             HirStatement::Assign(assign_stmt) => self.check_assign_stmt(assign_stmt, stmt_id),
+            HirStatement::Return(value) => self.check_return_stmt(value),
             HirStatement::Error => (),
         }
         Type::Unit
@@ -58,7 +59,7 @@ impl<'interner> TypeChecker<'interner> {
         match pattern {
             HirPattern::Identifier(ident) => self.interner.push_definition_type(ident.id, typ),
             HirPattern::Mutable(pattern, _) => self.bind_pattern(pattern, typ),
-            HirPattern::Tuple(fields, span) => match typ {
+            HirPattern::Tuple(fields, span) => match typ.follow_bindings() {
                 Type::Tuple(field_types) if field_types.len() == fields.len() => {
                     for (field, field_type) in fields.iter().zip(field_types) {
                         self.bind_pattern(field, field_type);
@@ -66,8 +67,10 @@ impl<'interner> TypeChecker<'interner> {
                 }
                 Type::Error => (),
                 other => {
+                    // Only the number of fields is known from the pattern, e.g. `(_, _)`
+                    let expected_typ = format!("({})", vec!["_"; fields.len()].join(", "));
                     self.errors.push(TypeCheckError::TypeMismatch {
-                        expected_typ: other.to_string(),
+                        expected_typ,
                         expr_typ: other.to_string(),
                         expr_span: *span,
                     });
@@ -97,6 +100,50 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Checks the returned value, or `()` for a bare `return`, against the declared return
+    /// type of the enclosing function.
+    fn check_return_stmt(&mut self, value: Option<ExprId>) {
+        let function = match self.current_function {
+            Some(function) => function,
+            None => return,
+        };
+
+        let (value_type, span) = match value {
+            Some(value) => (self.check_expression(&value), self.interner.expr_span(&value)),
+            // Statements have no spans, so a bare `return` is reported on the function body
+            None => {
+                (Type::Unit, self.interner.expr_span(&self.interner.function(&function).as_expr()))
+            }
+        };
+
+        // The return type of a lambda is inferred from its body alone, which a return could
+        // not take part in, so returning from a lambda or from the function around it is
+        // not supported.
+        if self.in_lambda {
+            self.errors.push(TypeCheckError::Unstructured {
+                msg: "Return statements are not allowed within a lambda".into(),
+                span,
+            });
+            return;
+        }
+
+        if !self.is_unconstrained() {
+            self.errors.push(TypeCheckError::Unstructured {
+                msg: "Early returns are only allowed in unconstrained functions".into(),
+                span,
+            });
+        }
+
+        let declared_return_type = self.interner.function_meta(&function).return_type().clone();
+        self.make_subtype_of(&value_type, &declared_return_type, span, || {
+            TypeCheckError::TypeMismatch {
+                expected_typ: declared_return_type.to_string(),
+                expr_typ: value_type.to_string(),
+                expr_span: span,
+            }
+        });
+    }
+
     fn check_assign_stmt(&mut self, assign_stmt: HirAssignStatement, stmt_id: &StmtId) {
         let expr_type = self.check_expression(&assign_stmt.expression);
         let span = self.interner.expr_span(&assign_stmt.expression);
@@ -109,13 +156,12 @@ impl<'interner> TypeChecker<'interner> {
         });
 
         let span = self.interner.expr_span(&assign_stmt.expression);
-        self.make_subtype_of(&expr_type, &lvalue_type, span, || {
-            let msg = format!(
-                "Cannot assign an expression of type {expr_type} to a value of type {lvalue_type}"
-            );
-
-            TypeCheckError::Unstructured { msg, span }
+        self.make_subtype_of(&expr_type, &lvalue_type, span, || TypeCheckError::TypeMismatch {
+            expected_typ: lvalue_type.to_string(),
+            expr_typ: expr_type.to_string(),
+            expr_span: span,
         });
+        self.check_constant_fits(&assign_stmt.expression, &lvalue_type);
     }
 
     /// Type check an lvalue - the left hand side of an assignment statement.
@@ -145,11 +191,19 @@ impl<'interner> TypeChecker<'interner> {
                 let (result, object) = self.check_lvalue(*object, assign_span);
                 let object = Box::new(object);
 
-                let mut error = |typ| {
-                    self.errors.push(TypeCheckError::Unstructured {
-                        msg: format!("Type {typ} has no member named {field_name}"),
-                        span: field_name.span(),
-                    });
+                let mut error = |typ: Type| {
+                    let span = field_name.span();
+                    let error = match typ {
+                        Type::Struct(..) => {
+                            let field = field_name.0.contents.clone();
+                            TypeCheckError::UnknownField { typ, field, span }
+                        }
+                        _ => TypeCheckError::Unstructured {
+                            msg: format!("Type {typ} has no member named {field_name}"),
+                            span,
+                        },
+                    };
+                    self.errors.push(error);
                     (Type::Error, None)
                 };
 
@@ -185,7 +239,10 @@ impl<'interner> TypeChecker<'interner> {
                 let array = Box::new(array);
 
                 let typ = match result {
-                    Type::Array(_, elem_type) => *elem_type,
+                    Type::Array(length, elem_type) => {
+                        self.check_index_in_bounds(&index, &length);
+                        *elem_type
+                    }
                     Type::Error => Type::Error,
                     other => {
                         // TODO: Need a better span here
@@ -205,6 +262,7 @@ impl<'interner> TypeChecker<'interner> {
 
     fn check_let_stmt(&mut self, let_stmt: HirLetStatement) {
         let mut resolved_type = self.check_declaration(let_stmt.expression, let_stmt.r#type);
+        self.check_constant_fits(&let_stmt.expression, &resolved_type);
 
         resolved_type.set_comp_time_span(self.interner.expr_span(&let_stmt.expression));
 
@@ -217,11 +275,36 @@ impl<'interner> TypeChecker<'interner> {
         let expr_span = self.interner.expr_span(&stmt.0);
 
         let bool_type = Type::Bool(CompTime::new(self.interner));
-        self.unify(&expr_type, &bool_type, expr_span, || TypeCheckError::TypeMismatch {
-            expr_typ: expr_type.to_string(),
-            expected_typ: Type::Bool(CompTime::No(None)).to_string(),
-            expr_span,
+        self.unify(&expr_type, &bool_type, expr_span, || {
+            let error = TypeCheckError::TypeMismatch {
+                expr_typ: expr_type.to_string(),
+                expected_typ: Type::Bool(CompTime::No(None)).to_string(),
+                expr_span,
+            };
+
+            // A numeric condition was most likely meant to be compared against something
+            match expr_type.follow_bindings() {
+                Type::FieldElement(_) | Type::Integer(..) | Type::PolymorphicInteger(..) => error
+                    .add_context(
+                        "assertions expect a boolean, try adding a comparison such as `x == y`",
+                    ),
+                _ => error,
+            }
         });
+
+        if let Some(message) = stmt.2 {
+            let message_type = self.check_expression(&message);
+            let message_span = self.interner.expr_span(&message);
+
+            let string_type = Type::String(Box::new(self.interner.next_type_variable()));
+            self.unify(&message_type, &string_type, message_span, || {
+                TypeCheckError::TypeMismatch {
+                    expr_typ: message_type.to_string(),
+                    expected_typ: "str".to_string(),
+                    expr_span: message_span,
+                }
+            });
+        }
     }
 
     /// All declaration statements check that the user specified type(UST) is equal to the
@@ -21,12 +21,14 @@ pub enum HirExpression {
     Prefix(HirPrefixExpression),
     Infix(HirInfixExpression),
     Index(HirIndexExpression),
+    Slice(HirSliceExpression),
     Constructor(HirConstructorExpression),
     MemberAccess(HirMemberAccess),
     Call(HirCallExpression),
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
     For(HirForExpression),
+    While(HirWhileExpression),
     If(HirIfExpression),
     Tuple(Vec<ExprId>),
     Lambda(HirLambda),
@@ -55,6 +57,12 @@ pub struct HirForExpression {
     pub block: ExprId,
 }
 
+#[derive(Debug, Clone)]
+pub struct HirWhileExpression {
+    pub condition: ExprId,
+    pub block: ExprId,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct HirBinaryOp {
     pub kind: BinaryOpKind,
@@ -79,6 +87,8 @@ pub enum HirLiteral {
     Array(HirArrayLiteral),
     Bool(bool),
     Integer(FieldElement),
+    /// An integer literal written with the `_Field` suffix, which is always a Field
+    Field(FieldElement),
     Str(String),
 }
 
@@ -129,6 +139,9 @@ pub struct HirCastExpression {
 pub struct HirCallExpression {
     pub func: ExprId,
     pub arguments: Vec<ExprId>,
+    /// Set for calls with named arguments such as `foo(x: 1, y: 2)`. The type checker
+    /// reorders these arguments to match the parameters and then clears the names.
+    pub argument_names: Option<Vec<Ident>>,
     pub location: Location,
 }
 
@@ -150,15 +163,17 @@ impl HirMethodCallExpression {
         func: FuncId,
         location: Location,
         interner: &mut NodeInterner,
-    ) -> (ExprId, HirExpression) {
+    ) -> (ExprId, HirCallExpression) {
         let mut arguments = vec![self.object];
         arguments.append(&mut self.arguments);
 
         let id = interner.function_definition_id(func);
         let ident = HirExpression::Ident(HirIdent { location, id });
         let func = interner.push_expr(ident);
+        interner.push_expr_location(func, location.span, location.file);
 
-        (func, HirExpression::Call(HirCallExpression { func, arguments, location }))
+        let call = HirCallExpression { func, arguments, argument_names: None, location };
+        (func, call)
     }
 }
 
@@ -182,6 +197,14 @@ pub struct HirIndexExpression {
     pub index: ExprId,
 }
 
+/// Slicing, as in `array[start..end]`
+#[derive(Debug, Clone)]
+pub struct HirSliceExpression {
+    pub collection: ExprId,
+    pub start: ExprId,
+    pub end: ExprId,
+}
+
 #[derive(Debug, Clone)]
 pub struct HirBlockExpression(pub Vec<StmtId>);
 
@@ -40,7 +40,10 @@ pub struct Param(pub HirPattern, pub Type, pub noirc_abi::AbiVisibility);
 
 /// Attempts to retrieve the name of this parameter. Returns None
 /// if this parameter is a tuple or struct pattern.
-fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Option<&'a str> {
+pub(crate) fn get_param_name<'a>(
+    pattern: &HirPattern,
+    interner: &'a NodeInterner,
+) -> Option<&'a str> {
     match pattern {
         HirPattern::Identifier(ident) => Some(interner.definition_name(ident.id)),
         HirPattern::Mutable(pattern, _) => get_param_name(pattern, interner),
@@ -129,6 +132,10 @@ pub struct FuncMeta {
 
     pub parameters: Parameters,
 
+    /// The default value of each parameter, if it has one. Calls may leave out
+    /// the arguments for these parameters.
+    pub parameter_defaults: Vec<Option<ExprId>>,
+
     pub return_visibility: AbiVisibility,
 
     pub return_distinctness: AbiDistinctness,
@@ -15,6 +15,7 @@ pub enum HirStatement {
     Assign(HirAssignStatement),
     Expression(ExprId),
     Semi(ExprId),
+    Return(Option<ExprId>),
     Error,
 }
 
@@ -41,12 +42,12 @@ pub struct HirAssignStatement {
     pub expression: ExprId,
 }
 
-/// Corresponds to `constrain expr;` in the source code.
+/// Corresponds to `constrain expr;` or `assert(expr, message);` in the source code.
 /// This node also contains the FileId of the file the constrain
 /// originates from. This is used later in the SSA pass to issue
 /// an error if a constrain is found to be always false.
 #[derive(Debug, Clone)]
-pub struct HirConstrainStatement(pub ExprId, pub FileId);
+pub struct HirConstrainStatement(pub ExprId, pub FileId, pub Option<ExprId>);
 
 #[derive(Debug, Clone)]
 pub enum HirPattern {
@@ -57,6 +58,15 @@ pub enum HirPattern {
 }
 
 impl HirPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            HirPattern::Identifier(ident) => ident.location.span,
+            HirPattern::Mutable(_, span)
+            | HirPattern::Tuple(_, span)
+            | HirPattern::Struct(_, _, span) => *span,
+        }
+    }
+
     pub fn field_count(&self) -> usize {
         match self {
             HirPattern::Identifier(_) => 0,
@@ -8,6 +8,7 @@ use crate::{hir::type_check::TypeCheckError, node_interner::NodeInterner};
 use iter_extended::{btree_map, vecmap};
 use noirc_abi::AbiType;
 use noirc_errors::Span;
+use serde::{Deserialize, Serialize};
 
 use crate::{node_interner::StructId, Ident, Signedness};
 
@@ -31,8 +32,13 @@ pub enum Type {
 
     /// String(N) is an array of characters of length N. It is expected that N
     /// is either a type variable of some kind or a Type::Constant.
+    /// Strings are distinct from arrays of bytes and never unify with them.
     String(Box<Type>),
 
+    /// A single character of a string, as produced by indexing one. Characters are
+    /// represented as their utf-8 byte once monomorphized but only support comparisons.
+    Char,
+
     /// The unit type `()`.
     Unit,
 
@@ -86,6 +92,10 @@ pub enum Type {
     /// bind to an integer without special checks to bind it to a non-type.
     Constant(u64),
 
+    /// Type-level arithmetic over type-level integers, such as the `N + 1` in `[u8; N + 1]`.
+    /// This is folded into a Type::Constant once both of its operands are known.
+    InfixExpr(Box<Type>, BinaryTypeOperator, Box<Type>),
+
     /// The result of some type error. Remembering type errors as their own type variant lets
     /// us avoid issuing repeat type errors for the same item. For example, a lambda with
     /// an invalid type would otherwise issue a new error each time it is called
@@ -297,6 +307,21 @@ impl TypeBinding {
     }
 }
 
+/// The bindings given to the unbound type variables of a [`Type::snapshot`], so that
+/// unifying the copy leaves the original type unchanged.
+#[derive(Default)]
+struct BindingSnapshot {
+    type_variables: HashMap<TypeVariableId, TypeVariable>,
+    comptimes: HashMap<TypeVariableId, Shared<Option<CompTime>>>,
+}
+
+impl BindingSnapshot {
+    fn type_variable(&mut self, id: TypeVariableId) -> TypeVariable {
+        let copy = || Shared::new(TypeBinding::Unbound(id));
+        self.type_variables.entry(id).or_insert_with(copy).clone()
+    }
+}
+
 /// A unique ID used to differentiate different type variables
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct TypeVariableId(pub usize);
@@ -366,6 +391,20 @@ pub enum SpanKind {
 }
 
 impl CompTime {
+    /// Copy this CompTime for [`Type::snapshot`], giving an unbound `Maybe` a new binding
+    fn snapshot(&self, snapshot: &mut BindingSnapshot) -> CompTime {
+        match self {
+            CompTime::Maybe(id, binding) => match &*binding.borrow() {
+                Some(comptime) => comptime.snapshot(snapshot),
+                None => {
+                    let copy = snapshot.comptimes.entry(*id).or_insert_with(|| Shared::new(None));
+                    CompTime::Maybe(*id, copy.clone())
+                }
+            },
+            CompTime::Yes(_) | CompTime::No(_) => self.clone(),
+        }
+    }
+
     pub fn new(interner: &mut NodeInterner) -> Self {
         let id = interner.next_type_variable_id();
         Self::Maybe(id, Shared::new(None))
@@ -565,6 +604,7 @@ impl Type {
             Type::FieldElement(_)
             | Type::Integer(_, _, _)
             | Type::Bool(_)
+            | Type::Char
             | Type::String(_)
             | Type::Unit
             | Type::Error
@@ -575,7 +615,16 @@ impl Type {
             | Type::Forall(_, _) => false,
 
             Type::Array(length, elem) => {
-                elem.contains_numeric_typevar(target_id) || named_generic_id_matches_target(length)
+                elem.contains_numeric_typevar(target_id)
+                    || named_generic_id_matches_target(length)
+                    || length.contains_numeric_typevar(target_id)
+            }
+
+            Type::InfixExpr(lhs, _, rhs) => {
+                named_generic_id_matches_target(lhs)
+                    || named_generic_id_matches_target(rhs)
+                    || lhs.contains_numeric_typevar(target_id)
+                    || rhs.contains_numeric_typevar(target_id)
             }
 
             Type::Tuple(fields) => {
@@ -633,6 +682,7 @@ impl std::fmt::Display for Type {
                 write!(f, "({})", elements.join(", "))
             }
             Type::Bool(comp_time) => write!(f, "{comp_time}bool"),
+            Type::Char => write!(f, "char"),
             Type::String(len) => write!(f, "str<{len}>"),
             Type::Unit => write!(f, "()"),
             Type::Error => write!(f, "error"),
@@ -643,6 +693,7 @@ impl std::fmt::Display for Type {
                 TypeBinding::Unbound(_) => write!(f, "{name}"),
             },
             Type::Constant(x) => x.fmt(f),
+            Type::InfixExpr(lhs, op, rhs) => write!(f, "({lhs} {op} {rhs})"),
             Type::Forall(typevars, typ) => {
                 let typevars = vecmap(typevars, |(var, _)| var.to_string());
                 write!(f, "forall {}. {}", typevars.join(" "), typ)
@@ -916,6 +967,8 @@ impl Type {
                 other.try_bind_to(binding)
             }
 
+            (InfixExpr(..), _) | (_, InfixExpr(..)) => self.try_unify_infix_expr(other, span),
+
             (Array(len_a, elem_a), Array(len_b, elem_b)) => {
                 len_a.try_unify(len_b, span)?;
                 elem_a.try_unify(elem_b, span)
@@ -960,6 +1013,8 @@ impl Type {
 
             (Bool(comptime_a), Bool(comptime_b)) => comptime_a.unify(comptime_b, span),
 
+            (Char, Char) => Ok(()),
+
             (NamedGeneric(binding_a, name_a), NamedGeneric(binding_b, name_b)) => {
                 // Ensure NamedGenerics are never bound during type checking
                 assert!(binding_a.borrow().is_unbound());
@@ -996,6 +1051,41 @@ impl Type {
         }
     }
 
+    /// Unify two type-level integers where at least one is an InfixExpr. When an operand of
+    /// `N + c` or `N - c` is still unknown, N is solved for if the result is already known.
+    fn try_unify_infix_expr(&self, other: &Type, span: Span) -> Result<(), SpanKind> {
+        if let (Some(a), Some(b)) = (self.evaluate_to_u64(), other.evaluate_to_u64()) {
+            return if a == b { Ok(()) } else { Err(SpanKind::None) };
+        }
+
+        match (self, other) {
+            (Type::InfixExpr(lhs_a, op_a, rhs_a), Type::InfixExpr(lhs_b, op_b, rhs_b))
+                if op_a == op_b =>
+            {
+                lhs_a.try_unify(lhs_b, span)?;
+                rhs_a.try_unify(rhs_b, span)
+            }
+            (Type::InfixExpr(lhs, op, rhs), other) | (other, Type::InfixExpr(lhs, op, rhs)) => {
+                let (result, rhs) = match (other.evaluate_to_u64(), rhs.evaluate_to_u64()) {
+                    (Some(result), Some(rhs)) => (result, rhs),
+                    _ => return Err(SpanKind::None),
+                };
+
+                let solution = match op {
+                    BinaryTypeOperator::Addition => result.checked_sub(rhs),
+                    BinaryTypeOperator::Subtraction => result.checked_add(rhs),
+                    _ => None,
+                };
+
+                match solution {
+                    Some(solution) => lhs.try_unify(&Type::Constant(solution), span),
+                    None => Err(SpanKind::None),
+                }
+            }
+            _ => Err(SpanKind::None),
+        }
+    }
+
     /// The `subtype` term here is somewhat loose, the only sub-typing relations remaining
     /// have to do with CompTime tracking.
     pub fn make_subtype_of(
@@ -1049,6 +1139,9 @@ impl Type {
                 other.try_bind_to(binding)
             }
 
+            // Type-level integers are never comptime, so there is no subtyping relation to check
+            (InfixExpr(..), _) | (_, InfixExpr(..)) => self.try_unify_infix_expr(other, span),
+
             (Array(len_a, elem_a), Array(len_b, elem_b)) => {
                 len_a.is_subtype_of(len_b, span)?;
                 elem_a.is_subtype_of(elem_b, span)
@@ -1092,6 +1185,8 @@ impl Type {
 
             (Bool(comptime_a), Bool(comptime_b)) => comptime_a.is_subtype_of(comptime_b, span),
 
+            (Char, Char) => Ok(()),
+
             (NamedGeneric(binding_a, name_a), NamedGeneric(binding_b, name_b)) => {
                 // Ensure NamedGenerics are never bound during type checking
                 assert!(binding_a.borrow().is_unbound());
@@ -1129,6 +1224,67 @@ impl Type {
         }
     }
 
+    /// True if this type could be made a subtype of `expected` by [`Type::make_subtype_of`].
+    /// The check is done on copies of both types with their own type variable bindings,
+    /// so neither type is bound by it.
+    pub fn could_be_subtype_of(&self, expected: &Type) -> bool {
+        let mut snapshot = BindingSnapshot::default();
+        let actual = self.snapshot(&mut snapshot);
+        let expected = expected.snapshot(&mut snapshot);
+        actual.is_subtype_of(&expected, Span::default()).is_ok()
+    }
+
+    /// Copy this type, replacing each unbound type variable with a new variable of the same id.
+    /// Each variable is copied once, so a variable occurring twice is still shared in the copy.
+    fn snapshot(&self, snapshot: &mut BindingSnapshot) -> Type {
+        match self {
+            Type::FieldElement(comptime) => Type::FieldElement(comptime.snapshot(snapshot)),
+            Type::Integer(comptime, sign, bits) => {
+                Type::Integer(comptime.snapshot(snapshot), *sign, *bits)
+            }
+            Type::Bool(comptime) => Type::Bool(comptime.snapshot(snapshot)),
+            Type::PolymorphicInteger(comptime, binding) => match &*binding.borrow() {
+                TypeBinding::Bound(typ) => typ.snapshot(snapshot),
+                TypeBinding::Unbound(id) => {
+                    let binding = snapshot.type_variable(*id);
+                    Type::PolymorphicInteger(comptime.snapshot(snapshot), binding)
+                }
+            },
+            Type::TypeVariable(binding) => match &*binding.borrow() {
+                TypeBinding::Bound(typ) => typ.snapshot(snapshot),
+                TypeBinding::Unbound(id) => Type::TypeVariable(snapshot.type_variable(*id)),
+            },
+            Type::NamedGeneric(binding, name) => match &*binding.borrow() {
+                TypeBinding::Bound(typ) => typ.snapshot(snapshot),
+                TypeBinding::Unbound(id) => {
+                    Type::NamedGeneric(snapshot.type_variable(*id), name.clone())
+                }
+            },
+            Type::Array(length, element) => Type::Array(
+                Box::new(length.snapshot(snapshot)),
+                Box::new(element.snapshot(snapshot)),
+            ),
+            Type::String(length) => Type::String(Box::new(length.snapshot(snapshot))),
+            Type::Vec(element) => Type::Vec(Box::new(element.snapshot(snapshot))),
+            Type::Tuple(fields) => Type::Tuple(vecmap(fields, |field| field.snapshot(snapshot))),
+            Type::Struct(def, args) => {
+                Type::Struct(def.clone(), vecmap(args, |arg| arg.snapshot(snapshot)))
+            }
+            Type::Function(args, ret) => {
+                let args = vecmap(args, |arg| arg.snapshot(snapshot));
+                Type::Function(args, Box::new(ret.snapshot(snapshot)))
+            }
+            Type::InfixExpr(lhs, op, rhs) => Type::InfixExpr(
+                Box::new(lhs.snapshot(snapshot)),
+                *op,
+                Box::new(rhs.snapshot(snapshot)),
+            ),
+            Type::Char | Type::Unit | Type::Constant(_) | Type::Forall(..) | Type::Error => {
+                self.clone()
+            }
+        }
+    }
+
     /// If this type is a Type::Constant (used in array lengths), or is bound
     /// to a Type::Constant, return the constant as a u64.
     pub fn evaluate_to_u64(&self) -> Option<u64> {
@@ -1141,10 +1297,49 @@ impl Type {
             },
             Type::Array(len, _elem) => len.evaluate_to_u64(),
             Type::Constant(x) => Some(*x),
+            Type::InfixExpr(lhs, op, rhs) => {
+                op.checked_function()(lhs.evaluate_to_u64()?, rhs.evaluate_to_u64()?)
+            }
             _ => None,
         }
     }
 
+    /// Find an operation within this type, such as `N - 1` in `[u8; N - 1]`, whose operands
+    /// are known but which overflows, goes below zero, or divides by zero.
+    pub fn invalid_length_operation(&self) -> Option<(u64, BinaryTypeOperator, u64)> {
+        match self.follow_bindings() {
+            Type::InfixExpr(lhs, op, rhs) => match (lhs.evaluate_to_u64(), rhs.evaluate_to_u64()) {
+                (Some(lhs), Some(rhs)) if op.checked_function()(lhs, rhs).is_none() => {
+                    Some((lhs, op, rhs))
+                }
+                _ => lhs.invalid_length_operation().or_else(|| rhs.invalid_length_operation()),
+            },
+            Type::Array(length, element) => {
+                length.invalid_length_operation().or_else(|| element.invalid_length_operation())
+            }
+            Type::String(length) => length.invalid_length_operation(),
+            Type::Vec(element) => element.invalid_length_operation(),
+            Type::Tuple(fields) | Type::Struct(_, fields) => {
+                fields.iter().find_map(Type::invalid_length_operation)
+            }
+            Type::Function(args, ret) => args
+                .iter()
+                .find_map(Type::invalid_length_operation)
+                .or_else(|| ret.invalid_length_operation()),
+            Type::FieldElement(_)
+            | Type::Integer(..)
+            | Type::PolymorphicInteger(..)
+            | Type::Bool(_)
+            | Type::Char
+            | Type::Unit
+            | Type::TypeVariable(_)
+            | Type::NamedGeneric(..)
+            | Type::Constant(_)
+            | Type::Forall(..)
+            | Type::Error => None,
+        }
+    }
+
     // Note; use strict_eq instead of partial_eq when comparing field types
     // in this method, you most likely want to distinguish between public and private
     pub fn as_abi_type(&self) -> AbiType {
@@ -1169,6 +1364,7 @@ impl Type {
                 TypeBinding::Unbound(_) => Type::default_int_type(None).as_abi_type(),
             },
             Type::Bool(_) => AbiType::Boolean,
+            Type::Char => AbiType::Integer { sign: noirc_abi::Sign::Unsigned, width: 8 },
             Type::String(size) => {
                 let size = size
                     .evaluate_to_u64()
@@ -1178,6 +1374,7 @@ impl Type {
             Type::Error => unreachable!(),
             Type::Unit => unreachable!(),
             Type::Constant(_) => unreachable!(),
+            Type::InfixExpr(..) => unreachable!(),
             Type::Struct(def, args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
@@ -1193,6 +1390,47 @@ impl Type {
         }
     }
 
+    /// Converts this type into a self-contained form which can be serialized, e.g. to export
+    /// type check results. Type variables are replaced by what they are bound to, if anything.
+    pub fn as_exported_type(&self) -> ExportedType {
+        let length = |length: &Type| length.evaluate_to_u64();
+        match self.follow_bindings() {
+            Type::FieldElement(_) => ExportedType::Field,
+            Type::Integer(_, sign, width) => {
+                ExportedType::Integer { signed: sign == Signedness::Signed, width }
+            }
+            Type::Bool(_) => ExportedType::Bool,
+            Type::Char => ExportedType::Char,
+            Type::String(len) => ExportedType::String { length: length(&len) },
+            Type::Array(len, element) => ExportedType::Array {
+                length: length(&len),
+                element: Box::new(element.as_exported_type()),
+            },
+            Type::Vec(element) => {
+                ExportedType::Vec { element: Box::new(element.as_exported_type()) }
+            }
+            Type::Unit => ExportedType::Unit,
+            Type::Tuple(fields) => {
+                ExportedType::Tuple(vecmap(fields, |field| field.as_exported_type()))
+            }
+            Type::Struct(def, args) => ExportedType::Struct {
+                name: def.borrow().name.0.contents.clone(),
+                generics: vecmap(args, |arg| arg.as_exported_type()),
+            },
+            Type::Function(args, ret) => ExportedType::Function {
+                parameters: vecmap(args, |arg| arg.as_exported_type()),
+                return_type: Box::new(ret.as_exported_type()),
+            },
+            Type::NamedGeneric(_, name) => ExportedType::Generic { name: name.as_ref().clone() },
+            Type::Forall(_, typ) => typ.as_exported_type(),
+            Type::Constant(value) => ExportedType::Constant(value),
+            Type::TypeVariable(_) | Type::PolymorphicInteger(..) | Type::InfixExpr(..) => {
+                ExportedType::Unknown
+            }
+            Type::Error => ExportedType::Error,
+        }
+    }
+
     /// Iterate over the fields of this type.
     /// Panics if the type is not a struct or tuple.
     pub fn iter_fields(&self) -> impl Iterator<Item = (String, Type)> {
@@ -1304,10 +1542,16 @@ impl Type {
                 Type::Function(args, ret)
             }
             Type::Vec(element) => Type::Vec(Box::new(element.substitute(type_bindings))),
+            Type::InfixExpr(lhs, op, rhs) => {
+                let lhs = Box::new(lhs.substitute(type_bindings));
+                let rhs = Box::new(rhs.substitute(type_bindings));
+                Type::InfixExpr(lhs, *op, rhs)
+            }
 
             Type::FieldElement(_)
             | Type::Integer(_, _, _)
             | Type::Bool(_)
+            | Type::Char
             | Type::Constant(_)
             | Type::Error
             | Type::Unit => self.clone(),
@@ -1334,10 +1578,12 @@ impl Type {
                 args.iter().any(|arg| arg.occurs(target_id)) || ret.occurs(target_id)
             }
             Type::Vec(element) => element.occurs(target_id),
+            Type::InfixExpr(lhs, _, rhs) => lhs.occurs(target_id) || rhs.occurs(target_id),
 
             Type::FieldElement(_)
             | Type::Integer(_, _, _)
             | Type::Bool(_)
+            | Type::Char
             | Type::Constant(_)
             | Type::Error
             | Type::Unit => false,
@@ -1377,10 +1623,23 @@ impl Type {
             }
             Vec(element) => Vec(Box::new(element.follow_bindings())),
 
+            InfixExpr(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.follow_bindings(), rhs.follow_bindings());
+                // An operation which overflows is left unevaluated to be reported by the type checker
+                let folded = match (lhs.evaluate_to_u64(), rhs.evaluate_to_u64()) {
+                    (Some(a), Some(b)) => op.checked_function()(a, b),
+                    _ => None,
+                };
+                match folded {
+                    Some(value) => Constant(value),
+                    None => InfixExpr(Box::new(lhs), *op, Box::new(rhs)),
+                }
+            }
+
             // Expect that this function should only be called on instantiated types
             Forall(..) => unreachable!(),
 
-            FieldElement(_) | Integer(_, _, _) | Bool(_) | Constant(_) | Unit | Error => {
+            FieldElement(_) | Integer(_, _, _) | Bool(_) | Char | Constant(_) | Unit | Error => {
                 self.clone()
             }
         }
@@ -1388,14 +1647,141 @@ impl Type {
 }
 
 impl BinaryTypeOperator {
-    /// Return the actual rust numeric function associated with this operator
-    pub fn function(self) -> fn(u64, u64) -> u64 {
+    /// Return the actual rust numeric function associated with this operator.
+    /// It returns None if the operation overflows, goes below zero, or divides by zero
+    pub fn checked_function(self) -> fn(u64, u64) -> Option<u64> {
         match self {
-            BinaryTypeOperator::Addition => |a, b| a.wrapping_add(b),
-            BinaryTypeOperator::Subtraction => |a, b| a.wrapping_sub(b),
-            BinaryTypeOperator::Multiplication => |a, b| a.wrapping_mul(b),
-            BinaryTypeOperator::Division => |a, b| a.wrapping_div(b),
-            BinaryTypeOperator::Modulo => |a, b| a.wrapping_rem(b), // % b,
+            BinaryTypeOperator::Addition => |a, b| a.checked_add(b),
+            BinaryTypeOperator::Subtraction => |a, b| a.checked_sub(b),
+            BinaryTypeOperator::Multiplication => |a, b| a.checked_mul(b),
+            BinaryTypeOperator::Division => |a, b| a.checked_div(b),
+            BinaryTypeOperator::Modulo => |a, b| a.checked_rem(b),
         }
     }
 }
+
+/// A [`Type`] which no longer refers to the NodeInterner, so that it can be serialized.
+/// Struct types are referred to by name, and array or string lengths which are not known
+/// constants are left as `None`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ExportedType {
+    Field,
+    Integer {
+        signed: bool,
+        width: u32,
+    },
+    Bool,
+    Char,
+    String {
+        length: Option<u64>,
+    },
+    Array {
+        length: Option<u64>,
+        element: Box<ExportedType>,
+    },
+    Vec {
+        element: Box<ExportedType>,
+    },
+    Unit,
+    Tuple(Vec<ExportedType>),
+    Struct {
+        name: String,
+        generics: Vec<ExportedType>,
+    },
+    Function {
+        parameters: Vec<ExportedType>,
+        return_type: Box<ExportedType>,
+    },
+    Generic {
+        name: String,
+    },
+    Constant(u64),
+    /// A type which inference has not determined
+    Unknown,
+    Error,
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::Signedness;
+
+    use super::{CompTime, ExportedType, Shared, Type, TypeBinding, TypeBindings, TypeVariableId};
+
+    fn named_generic(id: usize, name: &str) -> (TypeVariableId, Type) {
+        let id = TypeVariableId(id);
+        let var = Shared::new(TypeBinding::Unbound(id));
+        (id, Type::NamedGeneric(var, Rc::new(name.to_owned())))
+    }
+
+    #[test]
+    fn substitute_nested_generics() {
+        let (t_id, t) = named_generic(0, "T");
+        let (n_id, n) = named_generic(1, "N");
+
+        // fn([(T, bool); N]) -> [T; N]
+        let tuple = Type::Tuple(vec![t.clone(), Type::Bool(CompTime::No(None))]);
+        let array_of_tuples = Type::Array(Box::new(n.clone()), Box::new(tuple));
+        let array = Type::Array(Box::new(n), Box::new(t));
+        let function = Type::Function(vec![array_of_tuples], Box::new(array));
+
+        let u8_type = Type::Integer(CompTime::No(None), Signedness::Unsigned, 8);
+        let mut bindings = TypeBindings::new();
+        for (id, typ) in [(t_id, u8_type.clone()), (n_id, Type::Constant(3))] {
+            bindings.insert(id, (Shared::new(TypeBinding::Unbound(id)), typ));
+        }
+
+        let tuple = Type::Tuple(vec![u8_type.clone(), Type::Bool(CompTime::No(None))]);
+        let array_of_tuples = Type::Array(Box::new(Type::Constant(3)), Box::new(tuple));
+        let array = Type::Array(Box::new(Type::Constant(3)), Box::new(u8_type));
+        let expected = Type::Function(vec![array_of_tuples], Box::new(array));
+
+        assert_eq!(function.substitute(&bindings), expected);
+    }
+
+    #[test]
+    fn substitute_leaves_unbound_generics() {
+        let (t_id, t) = named_generic(0, "T");
+        let (_, u) = named_generic(1, "U");
+
+        let mut bindings = TypeBindings::new();
+        bindings.insert(t_id, (Shared::new(TypeBinding::Unbound(t_id)), Type::Unit));
+
+        let tuple = Type::Tuple(vec![t, u.clone()]);
+        assert_eq!(tuple.substitute(&bindings), Type::Tuple(vec![Type::Unit, u]));
+    }
+
+    #[test]
+    fn exported_types_round_trip() {
+        let (_, t) = named_generic(0, "T");
+        let (n_id, n) = named_generic(1, "N");
+        let i32_type = Type::Integer(CompTime::No(None), Signedness::Signed, 32);
+        let generic_array = Type::Array(Box::new(n), Box::new(Type::Bool(CompTime::No(None))));
+        let bound = Type::TypeVariable(Shared::new(TypeBinding::Bound(i32_type.clone())));
+        let unbound = Type::TypeVariable(Shared::new(TypeBinding::Unbound(n_id)));
+        let function = Type::Function(vec![generic_array, t], Box::new(bound));
+        let tuple = Type::Tuple(vec![function, unbound, Type::String(Box::new(Type::Constant(3)))]);
+
+        let exported = tuple.as_exported_type();
+        let json = serde_json::to_string(&exported).unwrap();
+        assert_eq!(serde_json::from_str::<ExportedType>(&json).unwrap(), exported);
+
+        // Bindings are followed, and lengths which are not constants are left unknown
+        let expected = ExportedType::Tuple(vec![
+            ExportedType::Function {
+                parameters: vec![
+                    ExportedType::Array { length: None, element: Box::new(ExportedType::Bool) },
+                    ExportedType::Generic { name: "T".into() },
+                ],
+                return_type: Box::new(ExportedType::Integer { signed: true, width: 32 }),
+            },
+            ExportedType::Unknown,
+            ExportedType::String { length: Some(3) },
+        ]);
+        assert_eq!(exported, expected);
+
+        let json = serde_json::to_string(&i32_type.as_exported_type()).unwrap();
+        assert_eq!(json, r#"{"Integer":{"signed":true,"width":32}}"#);
+    }
+}
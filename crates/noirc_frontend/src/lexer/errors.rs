@@ -15,8 +15,12 @@ pub enum LexerErrorKind {
     InvalidIntegerLiteral { span: Span, found: String },
     #[error("MalformedFuncAttribute : {:?} is not a valid attribute", found)]
     MalformedFuncAttribute { span: Span, found: String },
+    #[error("LiteralExceedsModulus : {:?} is not smaller than the field modulus", found)]
+    LiteralExceedsModulus { span: Span, found: String },
     #[error("TooManyBits")]
     TooManyBits { span: Span, max: u32, got: u32 },
+    #[error("UnsupportedIntegerWidth")]
+    UnsupportedIntegerWidth { span: Span, max: u32, got: u32 },
     #[error("LogicalAnd used instead of bitwise and")]
     LogicalAnd { span: Span },
 }
@@ -28,7 +32,9 @@ impl LexerErrorKind {
             LexerErrorKind::NotADoubleChar { span, .. } => *span,
             LexerErrorKind::InvalidIntegerLiteral { span, .. } => *span,
             LexerErrorKind::MalformedFuncAttribute { span, .. } => *span,
+            LexerErrorKind::LiteralExceedsModulus { span, .. } => *span,
             LexerErrorKind::TooManyBits { span, .. } => *span,
+            LexerErrorKind::UnsupportedIntegerWidth { span, .. } => *span,
             LexerErrorKind::LogicalAnd { span } => *span,
         }
     }
@@ -61,6 +67,11 @@ impl LexerErrorKind {
                 format!(" {found} is not a valid attribute"),
                 *span,
             ),
+            LexerErrorKind::LiteralExceedsModulus { span, found } => (
+                "integer literal too large".to_string(),
+                format!(" {found} does not fit in a field element, it must be smaller than the field modulus"),
+                *span,
+            ),
             LexerErrorKind::TooManyBits { span, max, got } => (
                 "integer literal too large".to_string(),
                 format!(
@@ -68,6 +79,11 @@ impl LexerErrorKind {
                 ),
                 *span,
             ),
+            LexerErrorKind::UnsupportedIntegerWidth { span, max, got } => (
+                format!("unsupported integer type width {got}"),
+                format!("Integer types must have between 1 and {max} bits"),
+                *span,
+            ),
             LexerErrorKind::LogicalAnd { span } => (
                 "Noir has no logical-and (&&) operator since short-circuiting is much less efficient when compiling to circuits".to_string(),
                 "Try `&` instead, or use `if` only if you require short-circuiting".to_string(),
@@ -21,6 +21,9 @@ pub struct Lexer<'a> {
 
 pub type SpannedTokenResult = Result<SpannedToken, LexerErrorKind>;
 
+/// Marks an integer literal as a Field, see [`Token::FieldInt`]
+const FIELD_SUFFIX: &str = "_Field";
+
 impl<'a> Lexer<'a> {
     /// Given a source file of noir code, return all the tokens in the file
     /// in order, along with any lexing errors that occurred.
@@ -93,6 +96,7 @@ impl<'a> Lexer<'a> {
             Some('%') => self.single_char_token(Token::Percent),
             Some('^') => self.single_char_token(Token::Caret),
             Some(';') => self.single_char_token(Token::Semicolon),
+            Some('?') => self.single_char_token(Token::Question),
             Some('*') => self.single_char_token(Token::Star),
             Some('(') => self.single_char_token(Token::LeftParen),
             Some(')') => self.single_char_token(Token::RightParen),
@@ -310,6 +314,13 @@ impl<'a> Lexer<'a> {
             ch.is_ascii_digit() | ch.is_ascii_hexdigit() | (ch == 'x')
         });
 
+        // Values past the modulus would otherwise be rejected or silently wrap around,
+        // depending on how they are written
+        if exceeds_modulus(&integer_str) {
+            let span = Span::exclusive(start, end);
+            return Err(LexerErrorKind::LiteralExceedsModulus { span, found: integer_str });
+        }
+
         let integer = match FieldElement::try_from_str(&integer_str) {
             None => {
                 return Err(LexerErrorKind::InvalidIntegerLiteral {
@@ -320,6 +331,20 @@ impl<'a> Lexer<'a> {
             Some(integer) => integer,
         };
 
+        let suffix: String = self
+            .char_iter
+            .clone()
+            .map(|(ch, _)| ch)
+            .take_while(|ch| ch.is_ascii_alphanumeric() || *ch == '_')
+            .collect();
+
+        if suffix == FIELD_SUFFIX {
+            for _ in 0..FIELD_SUFFIX.len() {
+                self.next_char();
+            }
+            return Ok(Token::FieldInt(integer).into_span(start, self.position));
+        }
+
         let integer_token = Token::Int(integer);
         Ok(integer_token.into_span(start, end))
     }
@@ -353,6 +378,22 @@ impl<'a> Iterator for Lexer<'a> {
     }
 }
 
+/// True if the literal is a well-formed decimal or `0x` prefixed hexadecimal integer which
+/// is not smaller than the field modulus
+fn exceeds_modulus(literal: &str) -> bool {
+    let (digits, radix) = match literal.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (literal, 10),
+    };
+    if !digits.chars().all(|ch| ch.is_digit(radix)) {
+        return false;
+    }
+
+    let digits = digits.trim_start_matches('0').to_ascii_lowercase();
+    let modulus = FieldElement::modulus().to_str_radix(radix);
+    (digits.len(), digits.as_str()) >= (modulus.len(), modulus.as_str())
+}
+
 #[test]
 fn test_single_double_char() {
     let input = "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == << >>";
@@ -436,6 +477,19 @@ fn test_int_type() {
     }
 }
 
+#[test]
+fn test_unsupported_int_type_widths() {
+    let (tokens, errors) = Lexer::lex("u1 i37 u128");
+    assert!(errors.is_empty());
+    assert_eq!(tokens.0[1].token(), &Token::IntType(IntType::Signed(37)));
+
+    for input in ["u0", "i129", "u254"] {
+        let (_, errors) = Lexer::lex(input);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], LexerErrorKind::UnsupportedIntegerWidth { .. }));
+    }
+}
+
 #[test]
 fn test_arithmetic_sugar() {
     let input = "+= -= *= /= %=";
@@ -511,6 +565,39 @@ fn test_eat_hex_int() {
     }
 }
 
+#[test]
+fn test_eat_field_int() {
+    let input = "5_Field 0x05_Field 5_x";
+
+    let expected = vec![
+        Token::FieldInt(5_i128.into()),
+        Token::FieldInt(5_i128.into()),
+        Token::Int(5_i128.into()),
+        Token::Ident("_x".to_string()),
+    ];
+    let mut lexer = Lexer::new(input);
+
+    for token in expected.into_iter() {
+        let got = lexer.next_token().unwrap();
+        assert_eq!(got, token);
+    }
+}
+
+#[test]
+fn test_int_below_modulus() {
+    let below = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+    let (_, errors) = Lexer::lex(below);
+    assert!(errors.is_empty());
+
+    let modulus = FieldElement::modulus().to_string();
+    let above = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000002_Field";
+    for input in [modulus.as_str(), above] {
+        let (_, errors) = Lexer::lex(input);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], LexerErrorKind::LiteralExceedsModulus { .. }));
+    }
+}
+
 #[test]
 fn test_span() {
     let input = "let x = 5";
@@ -13,6 +13,9 @@ use crate::lexer::errors::LexerErrorKind;
 pub enum Token {
     Ident(String),
     Int(FieldElement),
+    /// An integer literal with the `_Field` suffix, e.g. `5_Field`, which is always a Field
+    /// rather than taking its type from context
+    FieldInt(FieldElement),
     Bool(bool),
     Str(String),
     Keyword(Keyword),
@@ -76,6 +79,8 @@ pub enum Token {
     Colon,
     /// ::
     DoubleColon,
+    /// ?
+    Question,
     /// ;
     Semicolon,
     /// !
@@ -145,6 +150,7 @@ impl fmt::Display for Token {
         match *self {
             Token::Ident(ref s) => write!(f, "{s}"),
             Token::Int(n) => write!(f, "{}", n.to_u128()),
+            Token::FieldInt(n) => write!(f, "{}_Field", n.to_u128()),
             Token::Bool(b) => write!(f, "{b}"),
             Token::Str(ref b) => write!(f, "{b}"),
             Token::Keyword(k) => write!(f, "{k}"),
@@ -179,6 +185,7 @@ impl fmt::Display for Token {
             Token::Comma => write!(f, ","),
             Token::Colon => write!(f, ":"),
             Token::DoubleColon => write!(f, "::"),
+            Token::Question => write!(f, "?"),
             Token::Semicolon => write!(f, ";"),
             Token::Assign => write!(f, "="),
             Token::Bang => write!(f, "!"),
@@ -215,7 +222,9 @@ impl Token {
     pub fn kind(&self) -> TokenKind {
         match *self {
             Token::Ident(_) => TokenKind::Ident,
-            Token::Int(_) | Token::Bool(_) | Token::Str(_) => TokenKind::Literal,
+            Token::Int(_) | Token::FieldInt(_) | Token::Bool(_) | Token::Str(_) => {
+                TokenKind::Literal
+            }
             Token::Keyword(_) => TokenKind::Keyword,
             Token::Attribute(_) => TokenKind::Attribute,
             ref tok => TokenKind::Token(tok.clone()),
@@ -281,6 +290,10 @@ impl fmt::Display for IntType {
     }
 }
 
+/// The widest integer type supported. Integer constants are represented as a u128
+/// by the backend, so wider integers cannot be evaluated.
+pub const MAX_INTEGER_BITS: u32 = 128;
+
 impl IntType {
     // XXX: Result<Option<Token, LexerErrorKind>
     // Is not the best API. We could split this into two functions. One that checks if the the
@@ -309,6 +322,11 @@ impl IntType {
             return Err(LexerErrorKind::TooManyBits { span, max: max_bits, got: str_as_u32 });
         }
 
+        if str_as_u32 == 0 || str_as_u32 > MAX_INTEGER_BITS {
+            let max = MAX_INTEGER_BITS;
+            return Err(LexerErrorKind::UnsupportedIntegerWidth { span, max, got: str_as_u32 });
+        }
+
         if is_signed {
             Ok(Some(Token::IntType(IntType::Signed(str_as_u32))))
         } else {
@@ -439,6 +457,7 @@ pub enum Keyword {
     String,
     Return,
     Struct,
+    Type,
     Unconstrained,
     Use,
     Vec,
@@ -474,6 +493,7 @@ impl fmt::Display for Keyword {
             Keyword::String => write!(f, "str"),
             Keyword::Return => write!(f, "return"),
             Keyword::Struct => write!(f, "struct"),
+            Keyword::Type => write!(f, "type"),
             Keyword::Unconstrained => write!(f, "unconstrained"),
             Keyword::Use => write!(f, "use"),
             Keyword::Vec => write!(f, "Vec"),
@@ -512,6 +532,7 @@ impl Keyword {
             "str" => Keyword::String,
             "return" => Keyword::Return,
             "struct" => Keyword::Struct,
+            "type" => Keyword::Type,
             "unconstrained" => Keyword::Unconstrained,
             "use" => Keyword::Use,
             "Vec" => Keyword::Vec,
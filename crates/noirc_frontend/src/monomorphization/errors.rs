@@ -0,0 +1,32 @@
+use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic, Location};
+use thiserror::Error;
+
+/// Errors for programs which type check but use a feature that cannot be lowered yet.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MonomorphizationError {
+    #[error("{feature} are not yet supported")]
+    UnsupportedFeature { feature: &'static str, location: Location },
+}
+
+impl MonomorphizationError {
+    pub fn into_file_diagnostic(self) -> FileDiagnostic {
+        let file = match &self {
+            MonomorphizationError::UnsupportedFeature { location, .. } => location.file,
+        };
+        Diagnostic::from(self).in_file(file)
+    }
+}
+
+impl From<MonomorphizationError> for Diagnostic {
+    fn from(error: MonomorphizationError) -> Diagnostic {
+        match error {
+            MonomorphizationError::UnsupportedFeature { feature, location } => {
+                Diagnostic::simple_error(
+                    format!("{feature} are not yet supported"),
+                    "This cannot be compiled yet, even in an unconstrained function".into(),
+                    location.span,
+                )
+            }
+        }
+    }
+}
@@ -25,8 +25,10 @@ use crate::{
 };
 
 use self::ast::{Definition, FuncId, Function, LocalId, Program};
+use self::errors::MonomorphizationError;
 
 pub mod ast;
+pub mod errors;
 pub mod printer;
 
 /// The context struct for the monomorphization pass.
@@ -59,6 +61,9 @@ struct Monomorphizer<'interner> {
 
     next_local_id: u32,
     next_function_id: u32,
+
+    /// Uses of features which type check but cannot be lowered yet
+    errors: Vec<MonomorphizationError>,
 }
 
 type HirType = crate::Type;
@@ -74,7 +79,13 @@ type HirType = crate::Type;
 /// Note that there is no requirement on the `main` function that can be passed into
 /// this function. Typically, this is the function named "main" in the source project,
 /// but it can also be, for example, an arbitrary test function for running `nargo test`.
-pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Program {
+///
+/// Returns every use of a feature that cannot be lowered yet, such as a `while` loop, as an
+/// error rather than a program.
+pub fn monomorphize(
+    main: node_interner::FuncId,
+    interner: &NodeInterner,
+) -> Result<Program, Vec<MonomorphizationError>> {
     let mut monomorphizer = Monomorphizer::new(interner);
     let function_sig = monomorphizer.compile_main(main);
 
@@ -87,9 +98,13 @@ pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Pro
         undo_instantiation_bindings(bindings);
     }
 
+    if !monomorphizer.errors.is_empty() {
+        return Err(monomorphizer.errors);
+    }
+
     let functions = vecmap(monomorphizer.finished_functions, |(_, f)| f);
     let FuncMeta { return_distinctness, .. } = interner.function_meta(&main);
-    Program::new(functions, function_sig, return_distinctness)
+    Ok(Program::new(functions, function_sig, return_distinctness))
 }
 
 impl<'interner> Monomorphizer<'interner> {
@@ -102,6 +117,7 @@ impl<'interner> Monomorphizer<'interner> {
             next_local_id: 0,
             next_function_id: 0,
             interner,
+            errors: Vec::new(),
         }
     }
 
@@ -245,7 +261,7 @@ impl<'interner> Monomorphizer<'interner> {
             HirExpression::Ident(ident) => self.ident(ident, expr),
             HirExpression::Literal(HirLiteral::Str(contents)) => Literal(Str(contents)),
             HirExpression::Literal(HirLiteral::Bool(value)) => Literal(Bool(value)),
-            HirExpression::Literal(HirLiteral::Integer(value)) => {
+            HirExpression::Literal(HirLiteral::Integer(value) | HirLiteral::Field(value)) => {
                 let typ = Self::convert_type(&self.interner.id_type(expr));
                 Literal(Integer(value, typ))
             }
@@ -255,7 +271,7 @@ impl<'interner> Monomorphizer<'interner> {
                     self.repeated_array(repeated_element, length)
                 }
             },
-            HirExpression::Block(block) => self.block(block.0),
+            HirExpression::Block(block) => self.block(expr, block.0),
 
             HirExpression::Prefix(prefix) => ast::Expression::Unary(ast::Unary {
                 operator: prefix.operator,
@@ -263,14 +279,48 @@ impl<'interner> Monomorphizer<'interner> {
             }),
 
             HirExpression::Infix(infix) => {
-                let lhs = Box::new(self.expr(infix.lhs));
-                let rhs = Box::new(self.expr(infix.rhs));
+                let mut lhs = Box::new(self.expr(infix.lhs));
+                let mut rhs = Box::new(self.expr(infix.rhs));
                 let operator = infix.operator.kind;
+
+                // The amount to shift by may be an unsigned integer of any bit size, but
+                // the operands of a shift are expected to have the same type after this point.
+                if operator.is_shift() {
+                    let lhs_type = Self::convert_type(&self.interner.id_type(infix.lhs));
+                    if Self::convert_type(&self.interner.id_type(infix.rhs)) != lhs_type {
+                        rhs = Box::new(ast::Expression::Cast(ast::Cast {
+                            lhs: rhs,
+                            r#type: lhs_type,
+                        }));
+                    }
+                } else {
+                    // When mixing signed and unsigned integers is allowed, the unsigned
+                    // operand is converted to the signed type of the same bit size.
+                    let lhs_type = Self::convert_type(&self.interner.id_type(infix.lhs));
+                    let rhs_type = Self::convert_type(&self.interner.id_type(infix.rhs));
+                    use crate::Signedness::{Signed, Unsigned};
+                    match (&lhs_type, &rhs_type) {
+                        (ast::Type::Integer(Unsigned, _), ast::Type::Integer(Signed, _)) => {
+                            lhs = Box::new(ast::Expression::Cast(ast::Cast {
+                                lhs,
+                                r#type: rhs_type,
+                            }));
+                        }
+                        (ast::Type::Integer(Signed, _), ast::Type::Integer(Unsigned, _)) => {
+                            rhs = Box::new(ast::Expression::Cast(ast::Cast {
+                                lhs: rhs,
+                                r#type: lhs_type,
+                            }));
+                        }
+                        _ => (),
+                    }
+                }
                 let location = self.interner.expr_location(&expr);
                 ast::Expression::Binary(ast::Binary { lhs, rhs, operator, location })
             }
 
             HirExpression::Index(index) => self.index(expr, index),
+            HirExpression::Slice(slice) => self.slice(expr, slice),
 
             HirExpression::MemberAccess(access) => {
                 let field_index = self.interner.get_field_index(expr);
@@ -280,10 +330,7 @@ impl<'interner> Monomorphizer<'interner> {
 
             HirExpression::Call(call) => self.function_call(call, expr),
 
-            HirExpression::Cast(cast) => ast::Expression::Cast(ast::Cast {
-                lhs: Box::new(self.expr(cast.lhs)),
-                r#type: Self::convert_type(&cast.r#type),
-            }),
+            HirExpression::Cast(cast) => self.cast(expr, cast),
 
             HirExpression::For(for_expr) => {
                 let start = self.expr(for_expr.start_range);
@@ -303,6 +350,11 @@ impl<'interner> Monomorphizer<'interner> {
                 })
             }
 
+            HirExpression::While(_) => {
+                let location = self.interner.expr_location(&expr);
+                self.unsupported("While loops", location)
+            }
+
             HirExpression::If(if_expr) => {
                 let cond = self.expr(if_expr.condition);
                 let then = self.expr(if_expr.consequence);
@@ -398,6 +450,103 @@ impl<'interner> Monomorphizer<'interner> {
         Self::aos_to_soa_index(collection, index, element_type, location)
     }
 
+    /// Slices with literal bounds are expanded into an array of each element in the range:
+    /// `arr[1..3]` => `{ let tmp = arr; [tmp[1], tmp[2]] }`
+    fn slice(&mut self, id: node_interner::ExprId, slice: HirSliceExpression) -> ast::Expression {
+        let (length, element) = match self.interner.id_type(id).follow_bindings() {
+            HirType::Array(length, element) => (length.evaluate_to_u64().unwrap_or(0), element),
+            _ => unreachable!("Slices with runtime bounds are rejected during type checking"),
+        };
+        let start = self
+            .interner
+            .integer_literal(slice.start)
+            .expect("The start of a slice of known length should be a literal");
+
+        let fresh_id = self.next_local_id();
+        let location = self.interner.expr_location(&id);
+        let element_type = Self::convert_type(&element);
+
+        let array = ast::Expression::Ident(ast::Ident {
+            location: None,
+            mutable: false,
+            definition: Definition::Local(fresh_id),
+            name: "_".into(),
+            typ: Self::convert_type(&self.interner.id_type(slice.collection)),
+        });
+
+        let contents = vecmap(0..length as u128, |i| {
+            let index = ast::Literal::Integer((start + i).into(), ast::Type::Field);
+            let index = Box::new(ast::Expression::Literal(index));
+            Self::aos_to_soa_index(Box::new(array.clone()), index, element_type.clone(), location)
+        });
+
+        let definition = ast::Expression::Let(ast::Let {
+            id: fresh_id,
+            mutable: false,
+            name: "_".into(),
+            expression: Box::new(self.expr(slice.collection)),
+        });
+
+        let array = Self::aos_to_soa(contents, element_type);
+        ast::Expression::Block(vec![definition, array])
+    }
+
+    fn cast(&mut self, id: node_interner::ExprId, cast: HirCastExpression) -> ast::Expression {
+        let lhs_type = Self::convert_type(&self.interner.id_type(cast.lhs));
+        let lhs = self.expr(cast.lhs);
+        let location = self.interner.expr_location(&id);
+        self.cast_value(lhs, lhs_type, Self::convert_type(&cast.r#type), location)
+    }
+
+    fn cast_value(
+        &mut self,
+        lhs: ast::Expression,
+        lhs_type: ast::Type,
+        r#type: ast::Type,
+        location: Location,
+    ) -> ast::Expression {
+        match (lhs_type, r#type) {
+            // Array casts are performed element-wise, recursing into nested arrays:
+            // `arr as [u16; 2]` => `{ let tmp = arr; [tmp[0] as u16, tmp[1] as u16] }`
+            (ast::Type::Array(length, lhs_element), ast::Type::Array(_, element_type)) => {
+                let fresh_id = self.next_local_id();
+
+                let array = ast::Expression::Ident(ast::Ident {
+                    location: None,
+                    mutable: false,
+                    definition: Definition::Local(fresh_id),
+                    name: "_".into(),
+                    typ: ast::Type::Array(length, lhs_element.clone()),
+                });
+
+                let contents = vecmap(0..length, |i| {
+                    let index = ast::Literal::Integer((i as u128).into(), ast::Type::Field);
+                    let element = ast::Expression::Index(ast::Index {
+                        collection: Box::new(array.clone()),
+                        index: Box::new(ast::Expression::Literal(index)),
+                        element_type: (*lhs_element).clone(),
+                        location,
+                    });
+                    let (lhs_element, element_type) =
+                        ((*lhs_element).clone(), (*element_type).clone());
+                    self.cast_value(element, lhs_element, element_type, location)
+                });
+
+                let definition = ast::Expression::Let(ast::Let {
+                    id: fresh_id,
+                    mutable: false,
+                    name: "_".into(),
+                    expression: Box::new(lhs),
+                });
+
+                let element_type = *element_type;
+                let array = ast::Literal::Array(ast::ArrayLiteral { contents, element_type });
+                ast::Expression::Block(vec![definition, ast::Expression::Literal(array)])
+            }
+            (_, r#type) => ast::Expression::Cast(ast::Cast { lhs: Box::new(lhs), r#type }),
+        }
+    }
+
     /// Unpack an array index into an array of structs into a struct of arrays index if needed.
     /// E.g. transforms my_pair_array[i] into (my_pair1_array[i], my_pair2_array[i])
     fn aos_to_soa_index(
@@ -432,7 +581,15 @@ impl<'interner> Monomorphizer<'interner> {
         }
     }
 
-    fn statement(&mut self, id: StmtId) -> ast::Expression {
+    /// Reports a feature which cannot be lowered yet and returns an empty
+    /// block in its place so that the rest of the program is still checked.
+    fn unsupported(&mut self, feature: &'static str, location: Location) -> ast::Expression {
+        self.errors.push(MonomorphizationError::UnsupportedFeature { feature, location });
+        ast::Expression::Block(vec![])
+    }
+
+    /// The location of the enclosing block is used for statements without one of their own
+    fn statement(&mut self, id: StmtId, block: node_interner::ExprId) -> ast::Expression {
         match self.interner.statement(&id) {
             HirStatement::Let(let_statement) => self.let_statement(let_statement),
             HirStatement::Constrain(constrain) => {
@@ -443,6 +600,10 @@ impl<'interner> Monomorphizer<'interner> {
             HirStatement::Assign(assign) => self.assign(assign),
             HirStatement::Expression(expr) => self.expr(expr),
             HirStatement::Semi(expr) => ast::Expression::Semi(Box::new(self.expr(expr))),
+            HirStatement::Return(value) => {
+                let location = self.interner.expr_location(&value.unwrap_or(block));
+                self.unsupported("Early returns", location)
+            }
             HirStatement::Error => unreachable!(),
         }
     }
@@ -493,8 +654,8 @@ impl<'interner> Monomorphizer<'interner> {
         ast::Expression::Block(new_exprs)
     }
 
-    fn block(&mut self, statement_ids: Vec<StmtId>) -> ast::Expression {
-        ast::Expression::Block(vecmap(statement_ids, |id| self.statement(id)))
+    fn block(&mut self, id: node_interner::ExprId, statement_ids: Vec<StmtId>) -> ast::Expression {
+        ast::Expression::Block(vecmap(statement_ids, |statement| self.statement(statement, id)))
     }
 
     fn unpack_pattern(
@@ -620,6 +781,8 @@ impl<'interner> Monomorphizer<'interner> {
             HirType::Integer(_, sign, bits) => ast::Type::Integer(*sign, *bits),
             HirType::Bool(_) => ast::Type::Bool,
             HirType::String(size) => ast::Type::String(size.evaluate_to_u64().unwrap_or(0)),
+            // Strings are packed arrays of utf-8 bytes, so each of their characters is a u8
+            HirType::Char => ast::Type::Integer(crate::Signedness::Unsigned, 8),
             HirType::Unit => ast::Type::Unit,
 
             HirType::Array(length, element) => {
@@ -669,7 +832,10 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Type::Vec(Box::new(element))
             }
 
-            HirType::Forall(_, _) | HirType::Constant(_) | HirType::Error => {
+            HirType::Forall(_, _)
+            | HirType::Constant(_)
+            | HirType::InfixExpr(..)
+            | HirType::Error => {
                 unreachable!("Unexpected type {} found", typ)
             }
         }
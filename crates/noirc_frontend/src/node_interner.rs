@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use acvm::acir::circuit::opcodes::BlackBoxFuncCall;
 use acvm::acir::circuit::Opcode;
@@ -12,16 +12,19 @@ use crate::ast::Ident;
 use crate::graph::CrateId;
 use crate::hir::def_collector::dc_crate::UnresolvedStruct;
 use crate::hir::def_map::{LocalModuleId, ModuleId};
-use crate::hir::type_check::TypeCheckError;
+use crate::hir::type_check::{TypeCheckError, TypeCheckOptions};
 use crate::hir::StorageSlot;
 use crate::hir_def::stmt::HirLetStatement;
 use crate::hir_def::types::{StructType, Type};
 use crate::hir_def::{
-    expr::HirExpression,
+    expr::{
+        HirArrayLiteral, HirCastExpression, HirExpression, HirInfixExpression, HirLiteral,
+        HirPrefixExpression,
+    },
     function::{FuncMeta, HirFunction},
-    stmt::HirStatement,
+    stmt::{HirLValue, HirStatement},
 };
-use crate::{Shared, TypeBinding, TypeBindings, TypeVariable, TypeVariableId};
+use crate::{BinaryOpKind, Shared, TypeBinding, TypeBindings, TypeVariable, TypeVariableId};
 
 /// The node interner is the central storage location of all nodes in Noir's Hir (the
 /// various node types can be found in hir_def). The interner is also used to collect
@@ -37,6 +40,10 @@ pub struct NodeInterner {
     // Map each `Index` to it's own location
     id_to_location: HashMap<Index, Location>,
 
+    /// The span of each expression in each file, used to find the expression at a position.
+    /// See `expression_at`.
+    expression_spans: HashMap<FileId, Vec<(Span, ExprId)>>,
+
     // Maps each DefinitionId to a DefinitionInfo.
     definitions: Vec<DefinitionInfo>,
 
@@ -56,6 +63,12 @@ pub struct NodeInterner {
     // methods from impls to the type.
     structs: HashMap<StructId, Shared<StructType>>,
 
+    // Type alias map.
+    //
+    // Each alias is stored as the type it stands for so that name resolution can expand
+    // it in place. An alias is `None` until it has been resolved.
+    type_aliases: Vec<Option<Type>>,
+
     /// Map from ExprId (referring to a Function/Method call) to its corresponding TypeBindings,
     /// filled out during type checking from instantiated variables. Used during monomorphization
     /// to map call site types back onto function parameter types, and undo this binding as needed.
@@ -65,6 +78,9 @@ pub struct NodeInterner {
     /// checking.
     field_indices: HashMap<ExprId, usize>,
 
+    /// Arithmetic expressions which may overflow their type, see `requires_range_check`.
+    range_checks: HashSet<ExprId>,
+
     globals: HashMap<StmtId, GlobalInfo>, // NOTE: currently only used for checking repeat globals and restricting their scope to a module
 
     next_type_variable_id: usize,
@@ -72,6 +88,8 @@ pub struct NodeInterner {
     //used for fallback mechanism
     language: Language,
 
+    type_check_options: TypeCheckOptions,
+
     delayed_type_checks: Vec<TypeCheckFn>,
 
     /// A map from a struct type and method name to a function id for the method.
@@ -143,6 +161,15 @@ impl StructId {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct TypeAliasId(usize);
+
+impl TypeAliasId {
+    pub fn dummy_id() -> TypeAliasId {
+        TypeAliasId(std::usize::MAX)
+    }
+}
+
 macro_rules! into_index {
     ($id_type:ty) => {
         impl From<$id_type> for Index {
@@ -251,14 +278,18 @@ impl Default for NodeInterner {
             func_meta: HashMap::new(),
             function_definition_ids: HashMap::new(),
             id_to_location: HashMap::new(),
+            expression_spans: HashMap::new(),
             definitions: vec![],
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
+            type_aliases: Vec::new(),
             instantiation_bindings: HashMap::new(),
             field_indices: HashMap::new(),
+            range_checks: HashSet::new(),
             next_type_variable_id: 0,
             globals: HashMap::new(),
             language: Language::R1CS,
+            type_check_options: TypeCheckOptions::default(),
             delayed_type_checks: vec![],
             struct_methods: HashMap::new(),
             primitive_methods: HashMap::new(),
@@ -286,6 +317,23 @@ impl NodeInterner {
     /// Stores the span for an interned expression.
     pub fn push_expr_location(&mut self, expr_id: ExprId, span: Span, file: FileId) {
         self.id_to_location.insert(expr_id.into(), Location::new(span, file));
+        self.expression_spans.entry(file).or_default().push((span, expr_id));
+    }
+
+    /// Returns the innermost expression whose span contains the given location, if any.
+    /// When nested expressions share a span, the one interned first, which is the most
+    /// deeply nested, is returned.
+    pub fn expression_at(&self, location: Location) -> Option<ExprId> {
+        let contains = |span: &Span| {
+            span.start() <= location.span.start() && location.span.end() <= span.end()
+        };
+
+        self.expression_spans
+            .get(&location.file)?
+            .iter()
+            .filter(|(span, _)| contains(span))
+            .min_by_key(|(span, _)| span.end() - span.start())
+            .map(|(_, expr_id)| *expr_id)
     }
 
     /// Interns a HIR Function.
@@ -323,6 +371,20 @@ impl NodeInterner {
         f(&mut value);
     }
 
+    pub fn push_empty_type_alias(&mut self) -> TypeAliasId {
+        self.type_aliases.push(None);
+        TypeAliasId(self.type_aliases.len() - 1)
+    }
+
+    pub fn update_type_alias(&mut self, id: TypeAliasId, typ: Type) {
+        self.type_aliases[id.0] = Some(typ);
+    }
+
+    /// Returns the type a type alias stands for, or `None` if the alias has not been resolved yet.
+    pub fn get_type_alias(&self, id: TypeAliasId) -> Option<Type> {
+        self.type_aliases[id.0].clone()
+    }
+
     /// Returns the interned statement corresponding to `stmt_id`
     pub fn update_statement(&mut self, stmt_id: &StmtId, f: impl FnOnce(&mut HirStatement)) {
         let def =
@@ -446,6 +508,11 @@ impl NodeInterner {
         self.func_meta.get(func_id).cloned()
     }
 
+    /// Returns the id of every function with metadata, in no particular order
+    pub fn function_ids(&self) -> impl Iterator<Item = FuncId> + '_ {
+        self.func_meta.keys().copied()
+    }
+
     pub fn function_ident(&self, func_id: &FuncId) -> crate::Ident {
         let name = self.function_name(func_id).to_owned();
         let span = self.function_meta(func_id).name.location.span;
@@ -533,11 +600,226 @@ impl NodeInterner {
         self.id_to_type.get(&index.into()).cloned().unwrap_or(Type::Error)
     }
 
+    /// The value of the given expression if it is built only from integer literals and
+    /// arithmetic on them, as in `2 + 3`. Folding is conservative: any operation which would
+    /// overflow a u128, go below zero, or divide by zero leaves the expression unfolded.
+    pub fn constant_value(&self, expr_id: ExprId) -> Option<u128> {
+        match self.expression(&expr_id) {
+            HirExpression::Infix(infix) => {
+                let lhs = self.constant_value(infix.lhs)?;
+                let rhs = self.constant_value(infix.rhs)?;
+                fold_constant_infix(lhs, infix.operator.kind, rhs)
+            }
+            _ => self.integer_literal(expr_id),
+        }
+    }
+
+    /// The value of the given expression if it is an integer literal that fits in a u128
+    pub fn integer_literal(&self, expr_id: ExprId) -> Option<u128> {
+        match self.expression(&expr_id) {
+            HirExpression::Literal(HirLiteral::Integer(value)) if value.num_bits() <= 128 => {
+                Some(value.to_u128())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the type given to an expression by type checking, or None if the expression
+    /// has not been type checked. This is also None for expressions in a function whose
+    /// checking stopped before reaching them, such as the arguments of a call to an unknown method.
+    pub fn try_expr_type(&self, expr_id: ExprId) -> Option<Type> {
+        self.id_to_type.get(&expr_id.into()).cloned()
+    }
+
+    /// Returns every expression within the body of the given function, along with the type
+    /// it was assigned during type checking. Expressions are yielded parent-first, in the
+    /// order they occur in the source, and include the bodies of any lambdas.
+    pub fn expressions_in(&self, func_id: FuncId) -> impl Iterator<Item = (ExprId, Type)> + '_ {
+        let mut expressions = Vec::new();
+        self.collect_expressions(*self.function(&func_id).as_expr(), &mut expressions);
+        expressions.into_iter().map(|id| (id, self.id_type(id)))
+    }
+
+    fn collect_expressions(&self, expr_id: ExprId, expressions: &mut Vec<ExprId>) {
+        expressions.push(expr_id);
+
+        let mut children = Vec::new();
+        match self.expression(&expr_id) {
+            HirExpression::Ident(_) | HirExpression::Error => (),
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements))) => {
+                children = elements;
+            }
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Repeated {
+                repeated_element,
+                ..
+            })) => children.push(repeated_element),
+            HirExpression::Literal(_) => (),
+            HirExpression::Block(block) => {
+                for statement in block.statements() {
+                    self.collect_statement_expressions(*statement, expressions);
+                }
+            }
+            HirExpression::Prefix(prefix) => children.push(prefix.rhs),
+            HirExpression::Infix(infix) => children.extend([infix.lhs, infix.rhs]),
+            HirExpression::Index(index) => children.extend([index.collection, index.index]),
+            HirExpression::Slice(slice) => {
+                children.extend([slice.collection, slice.start, slice.end]);
+            }
+            HirExpression::Constructor(constructor) => {
+                children = vecmap(constructor.fields, |(_, field)| field);
+            }
+            HirExpression::MemberAccess(access) => children.push(access.lhs),
+            HirExpression::Call(call) => {
+                children.push(call.func);
+                children.extend(call.arguments);
+            }
+            HirExpression::MethodCall(call) => {
+                children.push(call.object);
+                children.extend(call.arguments);
+            }
+            HirExpression::Cast(cast) => children.push(cast.lhs),
+            HirExpression::For(for_expr) => {
+                children.extend([for_expr.start_range, for_expr.end_range, for_expr.block]);
+            }
+            HirExpression::While(while_expr) => {
+                children.extend([while_expr.condition, while_expr.block]);
+            }
+            HirExpression::If(if_expr) => {
+                children.extend([if_expr.condition, if_expr.consequence]);
+                children.extend(if_expr.alternative);
+            }
+            HirExpression::Tuple(elements) => children = elements,
+            HirExpression::Lambda(lambda) => children.push(lambda.body),
+        }
+
+        for child in children {
+            self.collect_expressions(child, expressions);
+        }
+    }
+
+    fn collect_statement_expressions(&self, stmt_id: StmtId, expressions: &mut Vec<ExprId>) {
+        match self.statement(&stmt_id) {
+            HirStatement::Let(let_stmt) => {
+                self.collect_expressions(let_stmt.expression, expressions)
+            }
+            HirStatement::Constrain(constrain) => {
+                self.collect_expressions(constrain.0, expressions);
+                if let Some(message) = constrain.2 {
+                    self.collect_expressions(message, expressions);
+                }
+            }
+            HirStatement::Assign(assign) => {
+                self.collect_lvalue_expressions(&assign.lvalue, expressions);
+                self.collect_expressions(assign.expression, expressions);
+            }
+            HirStatement::Expression(expr) | HirStatement::Semi(expr) => {
+                self.collect_expressions(expr, expressions);
+            }
+            HirStatement::Return(value) => {
+                if let Some(value) = value {
+                    self.collect_expressions(value, expressions);
+                }
+            }
+            HirStatement::Error => (),
+        }
+    }
+
+    fn collect_lvalue_expressions(&self, lvalue: &HirLValue, expressions: &mut Vec<ExprId>) {
+        match lvalue {
+            HirLValue::Ident(..) => (),
+            HirLValue::MemberAccess { object, .. } => {
+                self.collect_lvalue_expressions(object, expressions);
+            }
+            HirLValue::Index { array, index, .. } => {
+                self.collect_lvalue_expressions(array, expressions);
+                self.collect_expressions(*index, expressions);
+            }
+        }
+    }
+
+    /// Returns the given function along with every function it may transitively call.
+    /// Functions referred to without being called directly, such as those passed to a
+    /// higher-order function, are included as well. Method calls are only desugared into
+    /// function calls while type checking, so this is only complete once each function
+    /// has been type checked.
+    pub fn call_graph(&self, from: FuncId) -> HashSet<FuncId> {
+        let mut reachable = HashSet::new();
+        let mut queue = vec![from];
+
+        while let Some(func_id) = queue.pop() {
+            if !reachable.insert(func_id) {
+                continue;
+            }
+
+            for (expr_id, _) in self.expressions_in(func_id) {
+                if let HirExpression::Ident(ident) = self.expression(&expr_id) {
+                    if let DefinitionKind::Function(callee) = self.definition(ident.id).kind {
+                        queue.push(callee);
+                    }
+                }
+            }
+        }
+
+        reachable
+    }
+
     /// Returns the span of an item stored in the Interner
     pub fn id_location(&self, index: impl Into<Index>) -> Location {
         self.id_to_location.get(&index.into()).copied().unwrap()
     }
 
+    /// Interns a copy of the given expression and of every expression within it, keeping their
+    /// locations, so that the copy is type checked apart from the original. Only the expressions
+    /// which the resolver allows as default parameter values can be copied.
+    pub fn copy_expression(&mut self, expr_id: &ExprId) -> ExprId {
+        let copy = match self.expression(expr_id) {
+            expr @ (HirExpression::Ident(_)
+            | HirExpression::Error
+            | HirExpression::Literal(
+                HirLiteral::Bool(_)
+                | HirLiteral::Integer(_)
+                | HirLiteral::Field(_)
+                | HirLiteral::Str(_),
+            )) => expr,
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements))) => {
+                let elements = vecmap(&elements, |element| self.copy_expression(element));
+                HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements)))
+            }
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Repeated {
+                repeated_element,
+                length,
+            })) => {
+                let repeated_element = self.copy_expression(&repeated_element);
+                HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Repeated {
+                    repeated_element,
+                    length,
+                }))
+            }
+            HirExpression::Prefix(prefix) => HirExpression::Prefix(HirPrefixExpression {
+                operator: prefix.operator,
+                rhs: self.copy_expression(&prefix.rhs),
+            }),
+            HirExpression::Infix(infix) => HirExpression::Infix(HirInfixExpression {
+                lhs: self.copy_expression(&infix.lhs),
+                operator: infix.operator,
+                rhs: self.copy_expression(&infix.rhs),
+            }),
+            HirExpression::Cast(cast) => HirExpression::Cast(HirCastExpression {
+                lhs: self.copy_expression(&cast.lhs),
+                r#type: cast.r#type,
+            }),
+            HirExpression::Tuple(elements) => {
+                HirExpression::Tuple(vecmap(&elements, |element| self.copy_expression(element)))
+            }
+            other => unreachable!("Cannot copy the expression {other:?}"),
+        };
+
+        let location = self.expr_location(expr_id);
+        let copy_id = self.push_expr(copy);
+        self.push_expr_location(copy_id, location.span, location.file);
+        copy_id
+    }
+
     /// Replaces the HirExpression at the given ExprId with a new HirExpression
     pub fn replace_expr(&mut self, id: &ExprId, new: HirExpression) {
         let old = self.nodes.get_mut(id.into()).unwrap();
@@ -575,6 +857,18 @@ impl NodeInterner {
         self.field_indices.insert(expr_id, index);
     }
 
+    pub fn push_range_check(&mut self, expr_id: ExprId) {
+        self.range_checks.insert(expr_id);
+    }
+
+    /// True if the given expression is an integer operation whose result may overflow,
+    /// in which case the result must be range checked to fit within its integer type.
+    /// Field arithmetic wraps around the field modulus instead and never requires one.
+    pub fn requires_range_check(&self, expr_id: ExprId) -> bool {
+        self.range_checks.contains(&expr_id)
+            && matches!(self.id_type(expr_id).follow_bindings(), Type::Integer(..))
+    }
+
     pub fn function_definition_id(&self, function: FuncId) -> DefinitionId {
         self.function_definition_ids[&function]
     }
@@ -583,6 +877,14 @@ impl NodeInterner {
         self.language = language.clone();
     }
 
+    pub fn set_type_check_options(&mut self, options: TypeCheckOptions) {
+        self.type_check_options = options;
+    }
+
+    pub fn type_check_options(&self) -> &TypeCheckOptions {
+        &self.type_check_options
+    }
+
     #[allow(deprecated)]
     pub fn foreign(&self, opcode: &str) -> bool {
         let is_supported = acvm::default_is_opcode_supported(self.language.clone());
@@ -657,6 +959,18 @@ enum TypeMethodKey {
     Vec,
 }
 
+/// Evaluates an arithmetic operation on two constants, if its result is a valid u128
+fn fold_constant_infix(lhs: u128, operator: BinaryOpKind, rhs: u128) -> Option<u128> {
+    match operator {
+        BinaryOpKind::Add => lhs.checked_add(rhs),
+        BinaryOpKind::Subtract => lhs.checked_sub(rhs),
+        BinaryOpKind::Multiply => lhs.checked_mul(rhs),
+        BinaryOpKind::Divide => lhs.checked_div(rhs),
+        BinaryOpKind::Modulo => lhs.checked_rem(rhs),
+        _ => None,
+    }
+}
+
 fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
     use TypeMethodKey::*;
     let typ = typ.follow_bindings();
@@ -677,7 +991,9 @@ fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
         | Type::NamedGeneric(_, _)
         | Type::Forall(_, _)
         | Type::Constant(_)
+        | Type::InfixExpr(..)
         | Type::Error
+        | Type::Char
         | Type::Struct(_, _) => None,
     }
 }
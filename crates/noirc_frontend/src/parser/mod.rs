@@ -39,6 +39,7 @@ pub(crate) enum TopLevelStatement {
     Module(Ident),
     Import(ImportStatement),
     Struct(NoirStruct),
+    TypeAlias(NoirTypeAlias),
     Impl(NoirImpl),
     SubModule(SubModule),
     Global(LetStatement),
@@ -219,6 +220,7 @@ pub struct ParsedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub type_aliases: Vec<NoirTypeAlias>,
     pub impls: Vec<NoirImpl>,
     pub globals: Vec<LetStatement>,
 
@@ -247,6 +249,10 @@ impl ParsedModule {
         self.types.push(typ);
     }
 
+    fn push_type_alias(&mut self, alias: NoirTypeAlias) {
+        self.type_aliases.push(alias);
+    }
+
     fn push_impl(&mut self, r#impl: NoirImpl) {
         self.impls.push(r#impl);
     }
@@ -447,6 +453,7 @@ impl std::fmt::Display for TopLevelStatement {
             TopLevelStatement::Module(m) => write!(f, "mod {m}"),
             TopLevelStatement::Import(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
             TopLevelStatement::Global(c) => c.fmt(f),
@@ -473,6 +480,10 @@ impl std::fmt::Display for ParsedModule {
             write!(f, "{type_}")?;
         }
 
+        for alias in &self.type_aliases {
+            write!(f, "{alias}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
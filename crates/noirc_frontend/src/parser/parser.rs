@@ -35,7 +35,8 @@ use crate::token::{Attribute, Keyword, Token, TokenKind};
 use crate::{
     BinaryOp, BinaryOpKind, BlockExpression, CompTime, ConstrainStatement, FunctionDefinition,
     Ident, IfExpression, ImportStatement, InfixExpression, LValue, Lambda, NoirFunction, NoirImpl,
-    NoirStruct, Path, PathKind, Pattern, Recoverable, UnaryOp, UnresolvedTypeExpression,
+    NoirStruct, NoirTypeAlias, Path, PathKind, Pattern, Recoverable, UnaryOp,
+    UnresolvedTypeExpression, WhileExpression,
 };
 
 use chumsky::prelude::*;
@@ -77,6 +78,7 @@ fn module() -> impl NoirParser<ParsedModule> {
                     TopLevelStatement::Module(m) => program.push_module_decl(m),
                     TopLevelStatement::Import(i) => program.push_import(i),
                     TopLevelStatement::Struct(s) => program.push_type(s),
+                    TopLevelStatement::TypeAlias(t) => program.push_type_alias(t),
                     TopLevelStatement::Impl(i) => program.push_impl(i),
                     TopLevelStatement::SubModule(s) => program.push_submodule(s),
                     TopLevelStatement::Global(c) => program.push_global(c),
@@ -89,6 +91,7 @@ fn module() -> impl NoirParser<ParsedModule> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | type_alias_definition
 ///                    | implementation
 ///                    | submodule
 ///                    | module_declaration
@@ -100,6 +103,7 @@ fn top_level_statement(
     choice((
         function_definition(false).map(TopLevelStatement::Function),
         struct_definition(),
+        type_alias_definition().then_ignore(force(just(Token::Semicolon))),
         implementation(),
         submodule(module_parser.clone()),
         contract(module_parser),
@@ -166,6 +170,7 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
                 ),
                 body,
             )| {
+                let (parameters, parameter_defaults) = parameters.into_iter().unzip();
                 FunctionDefinition {
                     span: name.0.span(),
                     name,
@@ -174,6 +179,7 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
                     is_unconstrained,
                     generics,
                     parameters,
+                    parameter_defaults,
                     body,
                     return_type,
                     return_visibility,
@@ -229,6 +235,16 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
     )
 }
 
+/// type_alias_definition: 'type' ident '=' type
+fn type_alias_definition() -> impl NoirParser<TopLevelStatement> {
+    let p = ignore_then_commit(keyword(Keyword::Type), ident());
+    let p = then_commit_ignore(p, just(Token::Assign));
+    let p = then_commit(p, parse_type());
+    p.map_with_span(|(name, typ), span| {
+        TopLevelStatement::TypeAlias(NoirTypeAlias { name, typ, span })
+    })
+}
+
 fn lambda_return_type() -> impl NoirParser<UnresolvedType> {
     just(Token::Arrow)
         .ignore_then(parse_type())
@@ -276,19 +292,33 @@ fn lambda_parameters() -> impl NoirParser<Vec<(Pattern, UnresolvedType)>> {
     parameter.separated_by(just(Token::Comma)).allow_trailing().labelled("parameter")
 }
 
+/// function_parameters: function_parameter ',' function_parameters
+///                    | %empty
+///
+/// function_parameter: pattern ':' optional_visibility type default_value
+///                   | 'self'
+///
+/// default_value: '=' expression
+///              | %empty
 fn function_parameters<'a>(
     allow_self: bool,
-) -> impl NoirParser<Vec<(Pattern, UnresolvedType, AbiVisibility)>> + 'a {
+) -> impl NoirParser<Vec<((Pattern, UnresolvedType, AbiVisibility), Option<Expression>)>> + 'a {
     let typ = parse_type().recover_via(parameter_recovery());
+    let default_value = just(Token::Assign).ignore_then(expression()).or_not();
 
     let full_parameter = pattern()
         .recover_via(parameter_name_recovery())
         .then_ignore(just(Token::Colon))
         .then(optional_visibility())
         .then(typ)
-        .map(|((name, visibility), typ)| (name, typ, visibility));
+        .then(default_value)
+        .map(|(((name, visibility), typ), default)| ((name, typ, visibility), default));
 
-    let self_parameter = if allow_self { self_parameter().boxed() } else { nothing().boxed() };
+    let self_parameter = if allow_self {
+        self_parameter().map(|parameter| (parameter, None)).boxed()
+    } else {
+        nothing().boxed()
+    };
 
     let parameter = full_parameter.or(self_parameter);
 
@@ -438,16 +468,24 @@ where
         assertion(expr_parser.clone()),
         declaration(expr_parser.clone()),
         assignment(expr_parser.clone()),
+        return_statement(expr_parser.clone()),
         expr_parser.map(Statement::Expression),
     ))
 }
 
+fn return_statement<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
+where
+    P: ExprParser + 'a,
+{
+    keyword(Keyword::Return).ignore_then(expr_parser.or_not()).map(Statement::Return)
+}
+
 fn constrain<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
 where
     P: ExprParser + 'a,
 {
     ignore_then_commit(keyword(Keyword::Constrain).labelled("statement"), expr_parser)
-        .map(|expr| Statement::Constrain(ConstrainStatement(expr)))
+        .map(|expr| Statement::Constrain(ConstrainStatement(expr, None)))
         .validate(|expr, span, emit| {
             emit(ParserError::with_reason(ParserErrorReason::ConstrainDeprecated, span));
             expr
@@ -458,9 +496,14 @@ fn assertion<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
 where
     P: ExprParser + 'a,
 {
-    ignore_then_commit(keyword(Keyword::Assert), parenthesized(expr_parser))
+    let message = just(Token::Comma).ignore_then(expr_parser.clone()).or_not();
+    let arguments = expr_parser
+        .then(message)
+        .map(|(condition, message)| ConstrainStatement(condition, message));
+
+    ignore_then_commit(keyword(Keyword::Assert), parenthesized(arguments))
         .labelled("statement")
-        .map(|expr| Statement::Constrain(ConstrainStatement(expr)))
+        .map(Statement::Constrain)
 }
 
 fn declaration<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
@@ -681,8 +724,36 @@ where
 }
 
 fn expression() -> impl ExprParser {
-    recursive(|expr| expression_with_precedence(Precedence::Lowest, expr, false))
-        .labelled("expression")
+    recursive(|expr| {
+        let binary = expression_with_precedence(Precedence::Lowest, expr.clone(), false);
+        conditional(binary, expr)
+    })
+    .labelled("expression")
+}
+
+/// cond ? a : b
+///
+/// Sugar for `if cond { a } else { b }`. Unlike an `if`, both branches are always required.
+/// The conditional binds more loosely than any binary operator, and nested conditionals
+/// associate to the right.
+fn conditional<'a, P, E>(condition: P, expr_parser: E) -> impl NoirParser<Expression> + 'a
+where
+    P: NoirParser<Expression> + 'a,
+    E: ExprParser + 'a,
+{
+    let branches = just(Token::Question)
+        .ignore_then(expr_parser.clone())
+        .then_ignore(just(Token::Colon))
+        .then(expr_parser);
+
+    condition.then(branches.or_not()).map_with_span(|(condition, branches), span| match branches {
+        Some((consequence, alternative)) => {
+            let alternative = Some(alternative);
+            let if_expr = IfExpression { condition, consequence, alternative };
+            Expression::new(ExpressionKind::If(Box::new(if_expr)), span)
+        }
+        None => condition,
+    })
 }
 
 // An expression is a single term followed by 0 or more (OP subexpression)*
@@ -766,20 +837,25 @@ where
     P: ExprParser + 'a,
 {
     enum UnaryRhs {
-        Call(Vec<Expression>),
+        Call(Vec<(Option<Ident>, Expression)>),
         ArrayIndex(Expression),
+        ArraySlice(Expression, Expression),
         Cast(UnresolvedType),
         MemberAccess((Ident, Option<Vec<Expression>>)),
     }
 
     // `(arg1, ..., argN)` in `my_func(arg1, ..., argN)`
-    let call_rhs = parenthesized(expression_list(expr_parser.clone())).map(UnaryRhs::Call);
+    let call_rhs = parenthesized(call_arguments(expr_parser.clone())).map(UnaryRhs::Call);
 
-    // `[expr]` in `arr[expr]`
+    // `[expr]` in `arr[expr]`, or `[start..end]` in `arr[start..end]`
     let array_rhs = expr_parser
         .clone()
+        .then(just(Token::DoubleDot).ignore_then(expr_parser.clone()).or_not())
         .delimited_by(just(Token::LeftBracket), just(Token::RightBracket))
-        .map(UnaryRhs::ArrayIndex);
+        .map(|(index, end)| match end {
+            Some(end) => UnaryRhs::ArraySlice(index, end),
+            None => UnaryRhs::ArrayIndex(index),
+        });
 
     // `as Type` in `atom as Type`
     let cast_rhs =
@@ -797,6 +873,7 @@ where
     foldl_with_span(atom(expr_parser), rhs, |lhs, rhs, span| match rhs {
         UnaryRhs::Call(args) => Expression::call(lhs, args, span),
         UnaryRhs::ArrayIndex(index) => Expression::index(lhs, index, span),
+        UnaryRhs::ArraySlice(start, end) => Expression::slice(lhs, start, end, span),
         UnaryRhs::Cast(r#type) => Expression::cast(lhs, r#type, span),
         UnaryRhs::MemberAccess(field) => Expression::member_access_or_method_call(lhs, field, span),
     })
@@ -852,6 +929,15 @@ where
         .map_with_span(|((identifier, range), block), span| range.into_for(identifier, block, span))
 }
 
+fn while_expr<'a, P>(expr_parser: P) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+{
+    keyword(Keyword::While).ignore_then(expr_parser.clone()).then(block_expr(expr_parser)).map(
+        |(condition, block)| ExpressionKind::While(Box::new(WhileExpression { condition, block })),
+    )
+}
+
 /// The 'range' of a for loop. Either an actual range `start .. end` or an array expression.
 fn for_range<P>(expr_parser: P) -> impl NoirParser<ForRange>
 where
@@ -906,6 +992,27 @@ where
     expr_parser.separated_by(just(Token::Comma)).allow_trailing()
 }
 
+/// call_arguments: call_argument ',' call_arguments
+///               | %empty
+///
+/// call_argument: ident ':' expression
+///              | expression
+fn call_arguments<P>(expr_parser: P) -> impl NoirParser<Vec<(Option<Ident>, Expression)>>
+where
+    P: ExprParser,
+{
+    let name = ident().then_ignore(just(Token::Colon)).or_not();
+    name.then(expr_parser).separated_by(just(Token::Comma)).allow_trailing().validate(
+        |arguments, span, emit| {
+            let named = arguments.iter().filter(|(name, _)| name.is_some()).count();
+            if named != 0 && named != arguments.len() {
+                emit(ParserError::with_reason(ParserErrorReason::MixedNamedArguments, span));
+            }
+            arguments
+        },
+    )
+}
+
 fn not<P>(term_parser: P) -> impl NoirParser<ExpressionKind>
 where
     P: ExprParser,
@@ -929,6 +1036,7 @@ where
     choice((
         if_expr(expr_parser.clone()),
         for_expr(expr_parser.clone()),
+        while_expr(expr_parser.clone()),
         array_expr(expr_parser.clone()),
         constructor(expr_parser.clone()),
         lambda(expr_parser.clone()),
@@ -999,6 +1107,7 @@ fn variable() -> impl NoirParser<ExpressionKind> {
 fn literal() -> impl NoirParser<ExpressionKind> {
     token_kind(TokenKind::Literal).map(|token| match token {
         Token::Int(x) => ExpressionKind::integer(x),
+        Token::FieldInt(x) => ExpressionKind::field(x),
         Token::Bool(b) => ExpressionKind::boolean(b),
         Token::Str(s) => ExpressionKind::string(s),
         unexpected => unreachable!("Non-literal {} parsed as a literal", unexpected),
@@ -1110,8 +1219,12 @@ mod test {
             "hash (x,)",
             "(foo + bar)()",
             "(bar)()()()",
+            "foo(x: 1, y: a + b)",
+            "foo(y: bar(x: 2),)",
         ];
         parse_all(expression(), valid);
+
+        parse_all_failing(expression(), vec!["foo(x: 1, 2)", "foo(1, y: 2)"]);
     }
 
     #[test]
@@ -1135,6 +1248,23 @@ mod test {
         parse_all(atom_or_right_unary(expression()), valid);
     }
 
+    #[test]
+    fn parse_array_slice() {
+        let valid = vec!["x[0..2]", "y[a..a+3]", "m[1][i..j]", "foo.bar[1..3] as [u8; 2]"];
+        for expr in parse_all(atom_or_right_unary(expression()), valid) {
+            let is_slice = |expr: &Expression| matches!(expr.kind, ExpressionKind::Slice(_));
+            match &expr.kind {
+                ExpressionKind::Cast(cast) => assert!(is_slice(&cast.lhs)),
+                _ => assert!(is_slice(&expr)),
+            }
+        }
+
+        parse_all_failing(
+            atom_or_right_unary(expression()),
+            vec!["x[..2]", "x[0..]", "x[0..1..2]"],
+        );
+    }
+
     fn expr_to_array(expr: ExpressionKind) -> ArrayLiteral {
         let lit = match expr {
             ExpressionKind::Literal(literal) => literal,
@@ -1282,6 +1412,7 @@ mod test {
                 "assert((x ^ y) == y)",
                 "assert((x ^ y) == (y + m))",
                 "assert(x + x ^ x == y | m)",
+                "assert(x == y, \"message\")",
             ],
         );
     }
@@ -1296,6 +1427,12 @@ mod test {
         parse_all(declaration(expression()), vec!["let x = y", "let x : u8 = y"]);
     }
 
+    #[test]
+    fn parse_return() {
+        parse_all(return_statement(expression()), vec!["return", "return x + 1", "return (a, b)"]);
+        parse_all_failing(return_statement(expression()), vec!["return return", "return x y"]);
+    }
+
     #[test]
     fn parse_invalid_pub() {
         // pub cannot be used to declare a statement
@@ -1319,6 +1456,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_while_loop() {
+        parse_all(while_expr(expression()), vec!["while x < 10 {}", "while true { foo; bar }"]);
+
+        parse_all_failing(
+            while_expr(expression()),
+            vec![
+                "while {}",      // The condition is required
+                "while x < 10;", // The body must be a block
+            ],
+        );
+    }
+
     #[test]
     fn parse_function() {
         parse_all(
@@ -1330,6 +1480,7 @@ mod test {
                 "fn func_name(f: Field, y : pub Field, z : pub [u8;5],) {}",
                 "fn func_name(x: [Field], y : [Field;2],y : pub [Field;2], z : pub [u8;5])  {}",
                 "fn main(x: pub u8, y: pub u8) -> distinct pub [u8; 2] { [x, y] }",
+                "fn f(x: u8, scale: u8 = 1, offset: Field = N + 2) -> u8 { x * scale }",
             ],
         );
 
@@ -1363,6 +1514,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_conditional() {
+        parse_all(
+            expression(),
+            vec!["x ? 1 : 2", "x == y ? a + 1 : b", "x ? (y ? 1 : 2) : 3", "x ? 1 : y ? 2 : 3"],
+        );
+        parse_all_failing(expression(), vec!["x ? 1", "x ? : 2", "x ? 1 :"]);
+
+        // The condition is the whole comparison, not only its right hand side
+        let parsed = parse_with(expression(), "x == y ? 1 : 2").unwrap();
+        match parsed.kind {
+            ExpressionKind::If(if_expr) => {
+                assert!(matches!(if_expr.condition.kind, ExpressionKind::Infix(_)));
+                assert!(if_expr.alternative.is_some());
+            }
+            _ => unreachable!("expected the conditional to be parsed as an if expression"),
+        }
+    }
+
     fn expr_to_lit(expr: ExpressionKind) -> Literal {
         match expr {
             ExpressionKind::Literal(literal) => literal,
@@ -1481,6 +1651,15 @@ mod test {
         parse_all_failing(struct_definition(), failing);
     }
 
+    #[test]
+    fn parse_type_aliases() {
+        let cases = vec!["type Word = u32", "type Pair = (Field, Field)", "type Bytes = [u8; 32]"];
+        parse_all(type_alias_definition(), cases);
+
+        let failing = vec!["type = u32", "type Word u32", "type Word = "];
+        parse_all_failing(type_alias_definition(), failing);
+    }
+
     #[test]
     fn parse_member_access() {
         let cases = vec!["a.b", "a + b.c", "foo.bar as i32"];